@@ -26,6 +26,10 @@ pub enum ErrorKind {
     InvalidInput,
     /// An error kind indicating that a feature is not supported.
     UnsupportedFeature,
+    /// An error kind indicating that the CRC-32 stored in the extended header does not match the
+    /// CRC-32 of the tag data, which means that the tag has been corrupted. Contains the stored and
+    /// the computed CRC-32 respectively.
+    CrcMismatch(u32, u32),
 }
 
 /// A structure able to represent any error that may occur while performing metadata operations.
@@ -0,0 +1,239 @@
+/// The extended header of an ID3v2.3 or ID3v2.4 tag.
+///
+/// The extended header contains information about the tag that is not vital to the correct
+/// parsing of the frames. ID3v2.2 tags do not have an extended header.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExtendedHeader {
+    /// Indicates that the tag is an update of a tag found earlier in the file or stream. Frames in
+    /// this tag override the ones of the earlier tag.
+    ///
+    /// ID3v2.4 only.
+    pub is_update: bool,
+    /// The CRC-32 of the frame data.
+    ///
+    /// When a tag is read, this is set to the CRC-32 that was found in the extended header. When a
+    /// tag is encoded with this field set, the CRC-32 of the encoded frame data is computed and
+    /// written instead of the value of this field.
+    pub crc32: Option<u32>,
+    /// The restrictions that were imposed on the tag during encoding.
+    ///
+    /// ID3v2.4 only.
+    pub restrictions: Option<Restrictions>,
+}
+
+/// The restrictions an ID3v2.4 tag was encoded with.
+///
+/// These restrictions are intended to be used by encoders of tags that are targeted at devices
+/// with limited resources.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub struct Restrictions {
+    pub tag_size: TagSizeRestriction,
+    pub text_encoding: TextEncodingRestriction,
+    pub text_size: TextSizeRestriction,
+    pub image_encoding: ImageEncodingRestriction,
+    pub image_size: ImageSizeRestriction,
+}
+
+impl Default for Restrictions {
+    /// Returns the least strict restrictions.
+    fn default() -> Restrictions {
+        Restrictions {
+            tag_size: TagSizeRestriction::Frames128Size1M,
+            text_encoding: TextEncodingRestriction::None,
+            text_size: TextSizeRestriction::None,
+            image_encoding: ImageEncodingRestriction::None,
+            image_size: ImageSizeRestriction::None,
+        }
+    }
+}
+
+/// Restrictions on the number of frames in a tag and the total size of the tag.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TagSizeRestriction {
+    /// No more than 128 frames and 1 MB total tag size.
+    Frames128Size1M,
+    /// No more than 64 frames and 128 KB total tag size.
+    Frames64Size128K,
+    /// No more than 32 frames and 40 KB total tag size.
+    Frames32Size40K,
+    /// No more than 32 frames and 4 KB total tag size.
+    Frames32Size4K,
+}
+
+impl TagSizeRestriction {
+    /// Returns the maximum number of frames a tag may contain.
+    pub fn max_frames(self) -> usize {
+        match self {
+            TagSizeRestriction::Frames128Size1M => 128,
+            TagSizeRestriction::Frames64Size128K => 64,
+            TagSizeRestriction::Frames32Size40K | TagSizeRestriction::Frames32Size4K => 32,
+        }
+    }
+
+    /// Returns the maximum size of a tag in bytes, including its header.
+    pub fn max_size(self) -> usize {
+        match self {
+            TagSizeRestriction::Frames128Size1M => 1024 * 1024,
+            TagSizeRestriction::Frames64Size128K => 128 * 1024,
+            TagSizeRestriction::Frames32Size40K => 40 * 1024,
+            TagSizeRestriction::Frames32Size4K => 4 * 1024,
+        }
+    }
+}
+
+/// Restrictions on the text encodings used in a tag.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TextEncodingRestriction {
+    /// No restrictions.
+    None,
+    /// Strings are only encoded with ISO-8859-1 or UTF-8.
+    Latin1OrUTF8,
+}
+
+/// Restrictions on the length of the strings in a tag.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TextSizeRestriction {
+    /// No restrictions.
+    None,
+    /// No string is longer than 1024 characters.
+    Chars1024,
+    /// No string is longer than 128 characters.
+    Chars128,
+    /// No string is longer than 30 characters.
+    Chars30,
+}
+
+impl TextSizeRestriction {
+    /// Returns the maximum number of characters of a string or None if the length of strings is
+    /// not restricted.
+    pub fn max_chars(self) -> Option<usize> {
+        match self {
+            TextSizeRestriction::None => None,
+            TextSizeRestriction::Chars1024 => Some(1024),
+            TextSizeRestriction::Chars128 => Some(128),
+            TextSizeRestriction::Chars30 => Some(30),
+        }
+    }
+}
+
+/// Restrictions on the encoding of images in a tag.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImageEncodingRestriction {
+    /// No restrictions.
+    None,
+    /// Images are encoded only with PNG or JPEG.
+    PNGOrJPEG,
+}
+
+/// Restrictions on the dimensions of images in a tag.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImageSizeRestriction {
+    /// No restrictions.
+    None,
+    /// All images are 256x256 pixels or smaller.
+    Max256x256,
+    /// All images are 64x64 pixels or smaller.
+    Max64x64,
+    /// All images are exactly 64x64 pixels, unless required otherwise.
+    Exact64x64,
+}
+
+/// Determines what the `Encoder` does with a tag that violates the restrictions it is encoding
+/// with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RestrictionPolicy {
+    /// Encoding fails with an `InvalidInput` error describing the violated restriction.
+    Fail,
+    /// The tag is adapted to the restrictions: strings that are too long are truncated and frames
+    /// that can not be made to conform are left out.
+    Adapt,
+}
+
+impl From<u8> for Restrictions {
+    fn from(b: u8) -> Self {
+        Restrictions {
+            tag_size: match b >> 6 {
+                0 => TagSizeRestriction::Frames128Size1M,
+                1 => TagSizeRestriction::Frames64Size128K,
+                2 => TagSizeRestriction::Frames32Size40K,
+                _ => TagSizeRestriction::Frames32Size4K,
+            },
+            text_encoding: match (b >> 5) & 0x01 {
+                0 => TextEncodingRestriction::None,
+                _ => TextEncodingRestriction::Latin1OrUTF8,
+            },
+            text_size: match (b >> 3) & 0x03 {
+                0 => TextSizeRestriction::None,
+                1 => TextSizeRestriction::Chars1024,
+                2 => TextSizeRestriction::Chars128,
+                _ => TextSizeRestriction::Chars30,
+            },
+            image_encoding: match (b >> 2) & 0x01 {
+                0 => ImageEncodingRestriction::None,
+                _ => ImageEncodingRestriction::PNGOrJPEG,
+            },
+            image_size: match b & 0x03 {
+                0 => ImageSizeRestriction::None,
+                1 => ImageSizeRestriction::Max256x256,
+                2 => ImageSizeRestriction::Max64x64,
+                _ => ImageSizeRestriction::Exact64x64,
+            },
+        }
+    }
+}
+
+impl From<Restrictions> for u8 {
+    fn from(r: Restrictions) -> Self {
+        let tag_size = match r.tag_size {
+            TagSizeRestriction::Frames128Size1M => 0,
+            TagSizeRestriction::Frames64Size128K => 1,
+            TagSizeRestriction::Frames32Size40K => 2,
+            TagSizeRestriction::Frames32Size4K => 3,
+        };
+        let text_encoding = match r.text_encoding {
+            TextEncodingRestriction::None => 0,
+            TextEncodingRestriction::Latin1OrUTF8 => 1,
+        };
+        let text_size = match r.text_size {
+            TextSizeRestriction::None => 0,
+            TextSizeRestriction::Chars1024 => 1,
+            TextSizeRestriction::Chars128 => 2,
+            TextSizeRestriction::Chars30 => 3,
+        };
+        let image_encoding = match r.image_encoding {
+            ImageEncodingRestriction::None => 0,
+            ImageEncodingRestriction::PNGOrJPEG => 1,
+        };
+        let image_size = match r.image_size {
+            ImageSizeRestriction::None => 0,
+            ImageSizeRestriction::Max256x256 => 1,
+            ImageSizeRestriction::Max64x64 => 2,
+            ImageSizeRestriction::Exact64x64 => 3,
+        };
+        tag_size << 6 | text_encoding << 5 | text_size << 3 | image_encoding << 2 | image_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restrictions_byte() {
+        for b in 0..=0xff {
+            assert_eq!(b, u8::from(Restrictions::from(b)));
+        }
+        assert_eq!(
+            Restrictions {
+                tag_size: TagSizeRestriction::Frames32Size4K,
+                text_encoding: TextEncodingRestriction::Latin1OrUTF8,
+                text_size: TextSizeRestriction::Chars128,
+                image_encoding: ImageEncodingRestriction::None,
+                image_size: ImageSizeRestriction::Max256x256,
+            },
+            Restrictions::from(0b1111_0001)
+        );
+        assert_eq!(0, u8::from(Restrictions::default()));
+    }
+}
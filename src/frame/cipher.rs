@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A cipher that encrypts and decrypts the contents of frames.
+///
+/// Ciphers are registered in `Ciphers` under the method symbol of the ENCR frame that describes
+/// the encryption method. Ciphers must be `Send` and `Sync`, so that encoders holding them can be
+/// shared between threads.
+pub trait FrameCipher: Send + Sync {
+    /// Decrypts the data of the frame with the specified ID.
+    fn decrypt(&self, frame_id: &str, data: &[u8]) -> crate::Result<Vec<u8>>;
+    /// Encrypts the data of the frame with the specified ID.
+    fn encrypt(&self, frame_id: &str, data: &[u8]) -> crate::Result<Vec<u8>>;
+}
+
+/// A set of ciphers, indexed by their encryption method symbol.
+///
+/// # Example
+/// ```
+/// use id3::frame::{Ciphers, FrameCipher};
+///
+/// struct Xor(u8);
+///
+/// impl FrameCipher for Xor {
+///     fn decrypt(&self, _: &str, data: &[u8]) -> id3::Result<Vec<u8>> {
+///         Ok(data.iter().map(|b| b ^ self.0).collect())
+///     }
+///     fn encrypt(&self, frame_id: &str, data: &[u8]) -> id3::Result<Vec<u8>> {
+///         self.decrypt(frame_id, data)
+///     }
+/// }
+///
+/// let mut ciphers = Ciphers::new();
+/// ciphers.register(0x80, Xor(0x55));
+/// assert!(ciphers.get(0x80).is_some());
+/// assert!(ciphers.get(0x81).is_none());
+/// ```
+#[derive(Clone, Default)]
+pub struct Ciphers {
+    ciphers: HashMap<u8, Arc<dyn FrameCipher>>,
+}
+
+impl Ciphers {
+    /// Creates a new empty set of ciphers.
+    pub fn new() -> Ciphers {
+        Ciphers::default()
+    }
+
+    /// Registers the cipher for the specified method symbol, replacing any cipher that was
+    /// registered earlier for the same symbol.
+    pub fn register(&mut self, method_symbol: u8, cipher: impl FrameCipher + 'static) {
+        self.ciphers.insert(method_symbol, Arc::new(cipher));
+    }
+
+    /// Returns the cipher registered for the specified method symbol.
+    pub fn get(&self, method_symbol: u8) -> Option<&dyn FrameCipher> {
+        self.ciphers.get(&method_symbol).map(|cipher| &**cipher)
+    }
+}
+
+impl fmt::Debug for Ciphers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut symbols = self.ciphers.keys().collect::<Vec<_>>();
+        symbols.sort();
+        f.debug_struct("Ciphers")
+            .field("method_symbols", &symbols)
+            .finish()
+    }
+}
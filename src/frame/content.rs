@@ -1,3 +1,4 @@
+use crate::frame::Frame;
 use std::hash::{Hash, Hasher};
 
 /// The decoded contents of a frame.
@@ -19,6 +20,62 @@ pub enum Content {
     SynchronisedLyrics(SynchronisedLyrics),
     /// A value containing the parsed contents of a picture frame (APIC).
     Picture(Picture),
+    /// A value containing the parsed contents of a group identification registration frame
+    /// (GRID).
+    GroupIdentificationRegistration(GroupIdentificationRegistration),
+    /// A value containing the parsed contents of an encryption method registration frame (ENCR).
+    EncryptionMethodRegistration(EncryptionMethodRegistration),
+    /// A value containing the parsed contents of a unique file identifier frame (UFID).
+    UniqueFileIdentifier(UniqueFileIdentifier),
+    /// A value containing the parsed contents of a play counter frame (PCNT).
+    PlayCounter(u64),
+    /// A value containing the parsed contents of a popularimeter frame (POPM).
+    Popularimeter(Popularimeter),
+    /// A value containing the parsed contents of a general encapsulated object frame (GEOB).
+    EncapsulatedObject(EncapsulatedObject),
+    /// A value containing the parsed contents of a private frame (PRIV).
+    Private(Private),
+    /// A value containing the parsed contents of a chapter frame (CHAP).
+    Chapter(Chapter),
+    /// A value containing the parsed contents of a table of contents frame (CTOC).
+    TableOfContents(TableOfContents),
+    /// A value containing the parsed contents of a music CD identifier frame (MCDI).
+    MusicCdIdentifier(MusicCdIdentifier),
+    /// A value containing the parsed contents of an ID3v2.4 relative volume adjustment frame
+    /// (RVA2).
+    RelativeVolumeAdjustment(RelativeVolumeAdjustment),
+    /// A value containing the parsed contents of an ID3v2.2/ID3v2.3 relative volume adjustment
+    /// frame (RVAD).
+    VolumeAdjustment(VolumeAdjustment),
+    /// A value containing the parsed contents of an event timing codes frame (ETCO).
+    EventTimingCodes(EventTimingCodes),
+    /// A value containing the parsed contents of a synchronised tempo codes frame (SYTC).
+    SynchronisedTempoCodes(SynchronisedTempoCodes),
+    /// A value containing the parsed contents of an MPEG location lookup table frame (MLLT).
+    MpegLocationLookupTable(MpegLocationLookupTable),
+    /// A value containing the parsed contents of a position synchronisation frame (POSS).
+    PositionSynchronisation(PositionSynchronisation),
+    /// A value containing the parsed contents of a terms of use frame (USER).
+    TermsOfUse(TermsOfUse),
+    /// A value containing the parsed contents of an ownership frame (OWNE).
+    Ownership(Ownership),
+    /// A value containing the parsed contents of a commercial frame (COMR).
+    Commercial(Commercial),
+    /// A value containing the parsed contents of a linked information frame (LINK).
+    LinkedInformation(LinkedInformation),
+    /// A value containing the parsed contents of an audio seek point index frame (ASPI).
+    AudioSeekPointIndex(AudioSeekPointIndex),
+    /// A value containing the minimum offset in bytes from the end of the tag to the next tag,
+    /// as stored in a seek frame (SEEK).
+    SeekOffset(u32),
+    /// A value containing the parsed contents of an ID3v2.4 equalisation frame (EQU2).
+    Equalisation(Equalisation),
+    /// A value containing the parsed contents of an ID3v2.2/ID3v2.3 equalisation frame (EQUA).
+    LegacyEqualisation(LegacyEqualisation),
+    /// A value containing the parsed contents of a reverb frame (RVRB).
+    Reverb(Reverb),
+    /// A value containing the parsed contents of a recommended buffer size frame (RBUF).
+    RecommendedBufferSize(RecommendedBufferSize),
     /// A value containing the bytes of a unknown frame.
     Unknown(Vec<u8>),
 }
@@ -88,6 +145,221 @@ impl Content {
         }
     }
 
+    /// Returns the `GroupIdentificationRegistration` or None if the value is not
+    /// `GroupIdentificationRegistration`.
+    pub fn group_identification_registration(&self) -> Option<&GroupIdentificationRegistration> {
+        match *self {
+            Content::GroupIdentificationRegistration(ref grid) => Some(grid),
+            _ => None,
+        }
+    }
+
+    /// Returns the `EncryptionMethodRegistration` or None if the value is not
+    /// `EncryptionMethodRegistration`.
+    pub fn encryption_method_registration(&self) -> Option<&EncryptionMethodRegistration> {
+        match *self {
+            Content::EncryptionMethodRegistration(ref encr) => Some(encr),
+            _ => None,
+        }
+    }
+
+    /// Returns the `UniqueFileIdentifier` or None if the value is not `UniqueFileIdentifier`.
+    pub fn unique_file_identifier(&self) -> Option<&UniqueFileIdentifier> {
+        match *self {
+            Content::UniqueFileIdentifier(ref ufid) => Some(ufid),
+            _ => None,
+        }
+    }
+
+    /// Returns the `PlayCounter` or None if the value is not `PlayCounter`.
+    pub fn play_counter(&self) -> Option<u64> {
+        match *self {
+            Content::PlayCounter(counter) => Some(counter),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Popularimeter` or None if the value is not `Popularimeter`.
+    pub fn popularimeter(&self) -> Option<&Popularimeter> {
+        match *self {
+            Content::Popularimeter(ref popm) => Some(popm),
+            _ => None,
+        }
+    }
+
+    /// Returns the `EncapsulatedObject` or None if the value is not `EncapsulatedObject`.
+    pub fn encapsulated_object(&self) -> Option<&EncapsulatedObject> {
+        match *self {
+            Content::EncapsulatedObject(ref geob) => Some(geob),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Private` or None if the value is not `Private`.
+    pub fn private(&self) -> Option<&Private> {
+        match *self {
+            Content::Private(ref private) => Some(private),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Chapter` or None if the value is not `Chapter`.
+    pub fn chapter(&self) -> Option<&Chapter> {
+        match *self {
+            Content::Chapter(ref chapter) => Some(chapter),
+            _ => None,
+        }
+    }
+
+    /// Returns the `TableOfContents` or None if the value is not `TableOfContents`.
+    pub fn table_of_contents(&self) -> Option<&TableOfContents> {
+        match *self {
+            Content::TableOfContents(ref toc) => Some(toc),
+            _ => None,
+        }
+    }
+
+    /// Returns the `MusicCdIdentifier` or None if the value is not `MusicCdIdentifier`.
+    pub fn music_cd_identifier(&self) -> Option<&MusicCdIdentifier> {
+        match *self {
+            Content::MusicCdIdentifier(ref mcdi) => Some(mcdi),
+            _ => None,
+        }
+    }
+
+    /// Returns the `RelativeVolumeAdjustment` or None if the value is not
+    /// `RelativeVolumeAdjustment`.
+    pub fn relative_volume_adjustment(&self) -> Option<&RelativeVolumeAdjustment> {
+        match *self {
+            Content::RelativeVolumeAdjustment(ref rva2) => Some(rva2),
+            _ => None,
+        }
+    }
+
+    /// Returns the `VolumeAdjustment` or None if the value is not `VolumeAdjustment`.
+    pub fn volume_adjustment(&self) -> Option<&VolumeAdjustment> {
+        match *self {
+            Content::VolumeAdjustment(ref rvad) => Some(rvad),
+            _ => None,
+        }
+    }
+
+    /// Returns the `EventTimingCodes` or None if the value is not `EventTimingCodes`.
+    pub fn event_timing_codes(&self) -> Option<&EventTimingCodes> {
+        match *self {
+            Content::EventTimingCodes(ref etco) => Some(etco),
+            _ => None,
+        }
+    }
+
+    /// Returns the `SynchronisedTempoCodes` or None if the value is not
+    /// `SynchronisedTempoCodes`.
+    pub fn synchronised_tempo_codes(&self) -> Option<&SynchronisedTempoCodes> {
+        match *self {
+            Content::SynchronisedTempoCodes(ref sytc) => Some(sytc),
+            _ => None,
+        }
+    }
+
+    /// Returns the `MpegLocationLookupTable` or None if the value is not
+    /// `MpegLocationLookupTable`.
+    pub fn mpeg_location_lookup_table(&self) -> Option<&MpegLocationLookupTable> {
+        match *self {
+            Content::MpegLocationLookupTable(ref mllt) => Some(mllt),
+            _ => None,
+        }
+    }
+
+    /// Returns the `PositionSynchronisation` or None if the value is not
+    /// `PositionSynchronisation`.
+    pub fn position_synchronisation(&self) -> Option<&PositionSynchronisation> {
+        match *self {
+            Content::PositionSynchronisation(ref poss) => Some(poss),
+            _ => None,
+        }
+    }
+
+    /// Returns the `TermsOfUse` or None if the value is not `TermsOfUse`.
+    pub fn terms_of_use(&self) -> Option<&TermsOfUse> {
+        match *self {
+            Content::TermsOfUse(ref user) => Some(user),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Ownership` or None if the value is not `Ownership`.
+    pub fn ownership(&self) -> Option<&Ownership> {
+        match *self {
+            Content::Ownership(ref owne) => Some(owne),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Commercial` or None if the value is not `Commercial`.
+    pub fn commercial(&self) -> Option<&Commercial> {
+        match *self {
+            Content::Commercial(ref comr) => Some(comr),
+            _ => None,
+        }
+    }
+
+    /// Returns the `LinkedInformation` or None if the value is not `LinkedInformation`.
+    pub fn linked_information(&self) -> Option<&LinkedInformation> {
+        match *self {
+            Content::LinkedInformation(ref link) => Some(link),
+            _ => None,
+        }
+    }
+
+    /// Returns the `AudioSeekPointIndex` or None if the value is not `AudioSeekPointIndex`.
+    pub fn audio_seek_point_index(&self) -> Option<&AudioSeekPointIndex> {
+        match *self {
+            Content::AudioSeekPointIndex(ref aspi) => Some(aspi),
+            _ => None,
+        }
+    }
+
+    /// Returns the seek offset or None if the value is not `SeekOffset`.
+    pub fn seek_offset(&self) -> Option<u32> {
+        match *self {
+            Content::SeekOffset(offset) => Some(offset),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Equalisation` or None if the value is not `Equalisation`.
+    pub fn equalisation(&self) -> Option<&Equalisation> {
+        match *self {
+            Content::Equalisation(ref equ2) => Some(equ2),
+            _ => None,
+        }
+    }
+
+    /// Returns the `LegacyEqualisation` or None if the value is not `LegacyEqualisation`.
+    pub fn legacy_equalisation(&self) -> Option<&LegacyEqualisation> {
+        match *self {
+            Content::LegacyEqualisation(ref equa) => Some(equa),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Reverb` or None if the value is not `Reverb`.
+    pub fn reverb(&self) -> Option<&Reverb> {
+        match *self {
+            Content::Reverb(ref rvrb) => Some(rvrb),
+            _ => None,
+        }
+    }
+
+    /// Returns the `RecommendedBufferSize` or None if the value is not
+    /// `RecommendedBufferSize`.
+    pub fn recommended_buffer_size(&self) -> Option<&RecommendedBufferSize> {
+        match *self {
+            Content::RecommendedBufferSize(ref rbuf) => Some(rbuf),
+            _ => None,
+        }
+    }
+
     /// Returns the `Unknown` or None if the value is not `Unknown`.
     pub fn unknown(&self) -> Option<&[u8]> {
         match *self {
@@ -206,7 +478,1188 @@ impl Hash for SynchronisedLyrics {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// The parsed contents of a group identification registration frame.
+///
+/// Frames that belong to the registered group have their group identifier set to the group
+/// symbol.
+#[derive(Clone, Debug, Eq)]
+pub struct GroupIdentificationRegistration {
+    /// A URL or email address of the organisation responsible for the grouping.
+    pub owner_identifier: String,
+    /// The symbol that frames of this group are marked with. Values below 0x80 are reserved.
+    pub group_symbol: u8,
+    /// Data that is specific to the group.
+    pub data: Vec<u8>,
+}
+
+impl PartialEq for GroupIdentificationRegistration {
+    fn eq(&self, other: &Self) -> bool {
+        self.group_symbol == other.group_symbol
+    }
+}
+
+impl Hash for GroupIdentificationRegistration {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.group_symbol.hash(state);
+    }
+}
+
+/// The parsed contents of an encryption method registration frame.
+///
+/// Frames that are encrypted with the registered method have their encryption method set to the
+/// method symbol.
+#[derive(Clone, Debug, Eq)]
+pub struct EncryptionMethodRegistration {
+    /// A URL or email address of the organisation responsible for the encryption method.
+    pub owner_identifier: String,
+    /// The symbol that frames encrypted with this method are marked with. Values below 0x80 are
+    /// reserved.
+    pub method_symbol: u8,
+    /// Data that is specific to the encryption method.
+    pub data: Vec<u8>,
+}
+
+impl PartialEq for EncryptionMethodRegistration {
+    fn eq(&self, other: &Self) -> bool {
+        self.method_symbol == other.method_symbol
+    }
+}
+
+impl Hash for EncryptionMethodRegistration {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.method_symbol.hash(state);
+    }
+}
+
+/// The parsed contents of a unique file identifier frame.
+///
+/// A tag may contain only one identifier per owner.
+#[derive(Clone, Debug, Eq)]
+pub struct UniqueFileIdentifier {
+    /// A URL or email address of the organisation responsible for the database the identifier
+    /// belongs to.
+    pub owner_identifier: String,
+    /// The identifier of the file in the database, up to 64 bytes.
+    pub identifier: Vec<u8>,
+}
+
+impl PartialEq for UniqueFileIdentifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.owner_identifier == other.owner_identifier
+    }
+}
+
+impl Hash for UniqueFileIdentifier {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.owner_identifier.hash(state);
+    }
+}
+
+/// The parsed contents of a popularimeter frame.
+///
+/// A tag may contain only one popularimeter per user.
+#[derive(Clone, Debug, Eq)]
+pub struct Popularimeter {
+    /// The email address of the user the rating and counter belong to.
+    pub user: String,
+    /// The rating, from 1 (worst) to 255 (best). A rating of 0 means that the file is unrated.
+    pub rating: u8,
+    /// The number of times the file was played by the user. A counter of 0 is not written.
+    pub counter: u64,
+}
+
+impl PartialEq for Popularimeter {
+    fn eq(&self, other: &Self) -> bool {
+        self.user == other.user
+    }
+}
+
+impl Hash for Popularimeter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.user.hash(state);
+    }
+}
+
+/// The parsed contents of a general encapsulated object frame.
+///
+/// A tag may contain only one object per description.
+#[derive(Clone, Debug, Eq)]
+pub struct EncapsulatedObject {
+    /// The MIME type of the data.
+    pub mime_type: String,
+    /// The name of the file the object was encapsulated from.
+    pub filename: String,
+    /// A description of the object.
+    pub description: String,
+    /// The data of the object.
+    pub data: Vec<u8>,
+}
+
+impl PartialEq for EncapsulatedObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.description == other.description
+    }
+}
+
+impl Hash for EncapsulatedObject {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.description.hash(state);
+    }
+}
+
+/// The parsed contents of a private frame.
+///
+/// A tag may contain multiple private frames of the same owner as long as their data differs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Private {
+    /// A URL or email address of the organisation responsible for the frame.
+    pub owner_identifier: String,
+    /// The private data.
+    pub data: Vec<u8>,
+}
+
+/// The parsed contents of a chapter frame.
+///
+/// A tag may contain only one chapter per element ID.
+#[derive(Clone, Debug, Eq)]
+pub struct Chapter {
+    /// The element ID by which tables of contents refer to this chapter.
+    pub element_id: String,
+    /// The start of the chapter in milliseconds.
+    pub start_time: u32,
+    /// The end of the chapter in milliseconds.
+    pub end_time: u32,
+    /// The offset in bytes of the first audio frame of the chapter, counted from the start of the
+    /// file.
+    pub start_offset: Option<u32>,
+    /// The offset in bytes of the first audio frame after the chapter, counted from the start of
+    /// the file.
+    pub end_offset: Option<u32>,
+    /// The frames describing the chapter, such as its title (TIT2).
+    pub frames: Vec<Frame>,
+}
+
+/// The parsed contents of a table of contents frame.
+///
+/// A tag may contain only one table of contents per element ID.
+#[derive(Clone, Debug, Eq)]
+pub struct TableOfContents {
+    /// The element ID by which other tables of contents refer to this one.
+    pub element_id: String,
+    /// Whether this is the root of the table of contents tree. Only one table of contents in a
+    /// tag should be the top level.
+    pub top_level: bool,
+    /// Whether the child elements are ordered.
+    pub ordered: bool,
+    /// The element IDs of the child chapters and tables of contents.
+    pub elements: Vec<String>,
+    /// The frames describing the table of contents, such as its title (TIT2).
+    pub frames: Vec<Frame>,
+}
+
+impl PartialEq for Chapter {
+    fn eq(&self, other: &Self) -> bool {
+        self.element_id == other.element_id
+            && self.start_time == other.start_time
+            && self.end_time == other.end_time
+            && self.start_offset == other.start_offset
+            && self.end_offset == other.end_offset
+            && frames_eq(&self.frames, &other.frames)
+    }
+}
+
+impl Hash for Chapter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.element_id.hash(state);
+        self.start_time.hash(state);
+        self.end_time.hash(state);
+        self.start_offset.hash(state);
+        self.end_offset.hash(state);
+        hash_frames(&self.frames, state);
+    }
+}
+
+impl PartialEq for TableOfContents {
+    fn eq(&self, other: &Self) -> bool {
+        self.element_id == other.element_id
+            && self.top_level == other.top_level
+            && self.ordered == other.ordered
+            && self.elements == other.elements
+            && frames_eq(&self.frames, &other.frames)
+    }
+}
+
+impl Hash for TableOfContents {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.element_id.hash(state);
+        self.top_level.hash(state);
+        self.ordered.hash(state);
+        self.elements.hash(state);
+        hash_frames(&self.frames, state);
+    }
+}
+
+/// Compares embedded frames by their ID and their full content, unlike `Frame::eq` which only
+/// compares what makes a frame unique within a tag.
+fn frames_eq(a: &[Frame], b: &[Frame]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.id() == b.id() && a.content() == b.content())
+}
+
+fn hash_frames<H: Hasher>(frames: &[Frame], state: &mut H) {
+    frames.len().hash(state);
+    for frame in frames {
+        frame.id().hash(state);
+        frame.content().hash(state);
+    }
+}
+
+/// The parsed contents of a music CD identifier frame, which holds the table of contents of the
+/// CD the audio was taken from.
+///
+/// Offsets are expressed in CD frames of 1/75th of a second and include the lead-in of 150
+/// frames, as is common for disc ID calculations.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MusicCdIdentifier {
+    /// The number of the first track.
+    pub first_track: u8,
+    /// The number of the last track.
+    pub last_track: u8,
+    /// The track descriptors, followed by the descriptor of the lead-out.
+    pub tracks: Vec<CdTrack>,
+}
+
+/// A track descriptor in the table of contents of a CD.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CdTrack {
+    /// The number of the track or `CdTrack::LEAD_OUT`.
+    pub number: u8,
+    /// The ADR field in the upper and the control field in the lower 4 bits.
+    pub adr_control: u8,
+    /// The logical block address of the start of the track, which excludes the lead-in.
+    pub address: u32,
+}
+
+impl CdTrack {
+    /// The track number of the lead-out.
+    pub const LEAD_OUT: u8 = 0xAA;
+}
+
+/// The number of CD frames before the first logical block.
+const LEAD_IN: u32 = 150;
+
+impl MusicCdIdentifier {
+    /// Creates the table of contents of an audio CD from the offsets of its tracks and of its
+    /// lead-out.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::frame::MusicCdIdentifier;
+    ///
+    /// let mcdi = MusicCdIdentifier::from_offsets(1, &[150, 15363, 32314], 46592);
+    /// assert_eq!(mcdi.last_track, 3);
+    /// assert_eq!(mcdi.track_offsets(), vec![150, 15363, 32314]);
+    /// assert_eq!(mcdi.lead_out_offset(), Some(46592));
+    /// ```
+    pub fn from_offsets(
+        first_track: u8,
+        track_offsets: &[u32],
+        lead_out_offset: u32,
+    ) -> MusicCdIdentifier {
+        let track = |number, offset: u32| CdTrack {
+            number,
+            // ADR mode 1, audio track without pre-emphasis.
+            adr_control: 0x10,
+            address: offset.saturating_sub(LEAD_IN),
+        };
+        let mut tracks = track_offsets
+            .iter()
+            .enumerate()
+            .map(|(i, &offset)| track(first_track.wrapping_add(i as u8), offset))
+            .collect::<Vec<_>>();
+        tracks.push(track(CdTrack::LEAD_OUT, lead_out_offset));
+        MusicCdIdentifier {
+            first_track,
+            last_track: first_track
+                .wrapping_add(track_offsets.len() as u8)
+                .wrapping_sub(1),
+            tracks,
+        }
+    }
+
+    /// Returns the offsets of the tracks, excluding the lead-out.
+    ///
+    /// Offsets that do not fit in 32 bits because of the lead-in are saturated.
+    pub fn track_offsets(&self) -> Vec<u32> {
+        self.tracks
+            .iter()
+            .filter(|track| track.number != CdTrack::LEAD_OUT)
+            .map(|track| track.address.saturating_add(LEAD_IN))
+            .collect()
+    }
+
+    /// Returns the offset of the lead-out or None if the table of contents has no lead-out.
+    pub fn lead_out_offset(&self) -> Option<u32> {
+        self.tracks
+            .iter()
+            .find(|track| track.number == CdTrack::LEAD_OUT)
+            .map(|track| track.address.saturating_add(LEAD_IN))
+    }
+
+    /// Computes the FreeDB (CDDB) disc ID. Returns None if the table of contents has no tracks
+    /// or no lead-out.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::frame::MusicCdIdentifier;
+    ///
+    /// let offsets = [150, 15363, 32314, 46592, 63414, 80489];
+    /// let mcdi = MusicCdIdentifier::from_offsets(1, &offsets, 95462);
+    /// assert_eq!(mcdi.freedb_disc_id(), Some(0x3404_f606));
+    /// ```
+    pub fn freedb_disc_id(&self) -> Option<u32> {
+        let offsets = self.track_offsets();
+        let first_offset = *offsets.first()?;
+        let lead_out_offset = self.lead_out_offset()?;
+        let digit_sum = |mut n: u32| {
+            let mut sum = 0;
+            while n > 0 {
+                sum += n % 10;
+                n /= 10;
+            }
+            sum
+        };
+        let checksum: u32 = offsets.iter().map(|offset| digit_sum(offset / 75)).sum();
+        let length = (lead_out_offset / 75).saturating_sub(first_offset / 75);
+        Some((checksum % 0xFF) << 24 | length << 8 | offsets.len() as u32)
+    }
+
+    /// Computes the MusicBrainz disc ID. Returns None if the table of contents has no lead-out
+    /// or more than 99 tracks.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::frame::MusicCdIdentifier;
+    ///
+    /// let offsets = [150, 15363, 32314, 46592, 63414, 80489];
+    /// let mcdi = MusicCdIdentifier::from_offsets(1, &offsets, 95462);
+    /// assert_eq!(
+    ///     mcdi.musicbrainz_disc_id().unwrap(),
+    ///     "49HHV7Eb8UKF3aQiNmu1GR8vKTY-"
+    /// );
+    /// ```
+    pub fn musicbrainz_disc_id(&self) -> Option<String> {
+        let offsets = self.track_offsets();
+        if offsets.len() > 99 {
+            return None;
+        }
+        let mut toc = format!(
+            "{:02X}{:02X}{:08X}",
+            self.first_track,
+            self.last_track,
+            self.lead_out_offset()?
+        );
+        for i in 0..99 {
+            toc.push_str(&format!("{:08X}", offsets.get(i).cloned().unwrap_or(0)));
+        }
+        let digest = sha1_smol::Sha1::from(toc).digest().bytes();
+
+        // MusicBrainz uses base64 with an alphabet that is safe for URLs and filenames.
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789._";
+        let mut id = String::with_capacity(28);
+        for chunk in digest.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    id.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+                } else {
+                    id.push('-');
+                }
+            }
+        }
+        Some(id)
+    }
+}
+
+/// The parsed contents of an ID3v2.4 relative volume adjustment frame.
+///
+/// A tag may contain only one adjustment per identification. ReplayGain style adjustments use the
+/// identifications "track" and "album".
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RelativeVolumeAdjustment {
+    /// Identifies the situation or device the adjustment is meant for.
+    pub identification: String,
+    /// The adjustments of the individual channels.
+    pub channels: Vec<ChannelAdjustment>,
+}
+
+impl RelativeVolumeAdjustment {
+    /// Returns the adjustment of the specified channel.
+    pub fn channel(&self, channel_type: ChannelType) -> Option<&ChannelAdjustment> {
+        self.channels
+            .iter()
+            .find(|channel| channel.channel_type == channel_type)
+    }
+}
+
+/// The volume adjustment of a single channel in an RVA2 frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChannelAdjustment {
+    /// The channel that is adjusted.
+    pub channel_type: ChannelType,
+    /// The adjustment in units of 1/512 dB.
+    pub volume_adjustment: i16,
+    /// The number of bits of the peak volume. A value of 0 means that there is no peak volume.
+    pub peak_bits: u8,
+    /// The peak volume as a big-endian integer of `peak_bits` bits.
+    pub peak: Vec<u8>,
+}
+
+impl ChannelAdjustment {
+    /// Creates an adjustment of the specified channel by the specified amount of dB, without
+    /// a peak volume.
+    pub fn with_gain(channel_type: ChannelType, gain: f32) -> ChannelAdjustment {
+        let mut adjustment = ChannelAdjustment {
+            channel_type,
+            volume_adjustment: 0,
+            peak_bits: 0,
+            peak: Vec::new(),
+        };
+        adjustment.set_gain(gain);
+        adjustment
+    }
+
+    /// Returns the adjustment in dB.
+    pub fn gain(&self) -> f32 {
+        f32::from(self.volume_adjustment) / 512.0
+    }
+
+    /// Sets the adjustment in dB. Values are rounded to 1/512 dB and clamped to the range that
+    /// can be stored.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.volume_adjustment = (gain * 512.0)
+            .round()
+            .max(f32::from(i16::MIN))
+            .min(f32::from(i16::MAX)) as i16;
+    }
+
+    /// Returns the peak volume relative to full scale or None if no peak volume is stored.
+    pub fn peak_amplitude(&self) -> Option<f64> {
+        if self.peak_bits == 0 || self.peak_bits > 64 {
+            return None;
+        }
+        let peak = self
+            .peak
+            .iter()
+            .fold(0u64, |peak, &b| peak << 8 | u64::from(b));
+        // Like the samples it was measured from, the peak is a signed quantity, so full scale is
+        // reached at half the range.
+        Some(peak as f64 / 2f64.powi(i32::from(self.peak_bits) - 1))
+    }
+}
+
+/// Types of channels used in RVA2 frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum ChannelType {
+    Other,
+    MasterVolume,
+    FrontRight,
+    FrontLeft,
+    BackRight,
+    BackLeft,
+    FrontCentre,
+    BackCentre,
+    Subwoofer,
+    Undefined(u8),
+}
+
+impl From<ChannelType> for u8 {
+    fn from(ct: ChannelType) -> Self {
+        match ct {
+            ChannelType::Other => 0,
+            ChannelType::MasterVolume => 1,
+            ChannelType::FrontRight => 2,
+            ChannelType::FrontLeft => 3,
+            ChannelType::BackRight => 4,
+            ChannelType::BackLeft => 5,
+            ChannelType::FrontCentre => 6,
+            ChannelType::BackCentre => 7,
+            ChannelType::Subwoofer => 8,
+            ChannelType::Undefined(b) => b,
+        }
+    }
+}
+
+impl From<u8> for ChannelType {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => ChannelType::Other,
+            1 => ChannelType::MasterVolume,
+            2 => ChannelType::FrontRight,
+            3 => ChannelType::FrontLeft,
+            4 => ChannelType::BackRight,
+            5 => ChannelType::BackLeft,
+            6 => ChannelType::FrontCentre,
+            7 => ChannelType::BackCentre,
+            8 => ChannelType::Subwoofer,
+            b => ChannelType::Undefined(b),
+        }
+    }
+}
+
+/// The parsed contents of an ID3v2.2/ID3v2.3 relative volume adjustment frame.
+///
+/// The specification does not define the unit of the adjustments. This crate interprets an
+/// adjustment `v` as a change of the volume by a factor of `1 + v / 256`, which is inverted for
+/// decrements.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VolumeAdjustment {
+    /// The number of bits used for the adjustments and peaks, at most 64.
+    pub bits: u8,
+    /// The adjustment of the right channel.
+    pub right: VolumeChange,
+    /// The adjustment of the left channel.
+    pub left: VolumeChange,
+    /// The adjustment of the right back channel.
+    pub back_right: Option<VolumeChange>,
+    /// The adjustment of the left back channel.
+    pub back_left: Option<VolumeChange>,
+    /// The adjustment of the centre channel.
+    pub centre: Option<VolumeChange>,
+    /// The adjustment of the bass channel.
+    pub bass: Option<VolumeChange>,
+}
+
+/// The adjustment of a single channel in an RVAD frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct VolumeChange {
+    /// The relative volume change. Negative values decrease the volume.
+    pub adjustment: i64,
+    /// The peak volume.
+    pub peak: u64,
+}
+
+impl VolumeChange {
+    /// Returns the adjustment in dB.
+    pub fn gain(self) -> f32 {
+        let db = 20.0 * (1.0 + self.adjustment.unsigned_abs() as f64 / 256.0).log10();
+        (if self.adjustment < 0 { -db } else { db }) as f32
+    }
+
+    /// Creates a change of the volume by the specified amount of dB, without a peak volume.
+    pub fn with_gain(gain: f32) -> VolumeChange {
+        let adjustment = ((10f64.powf(f64::from(gain.abs()) / 20.0) - 1.0) * 256.0).round() as i64;
+        VolumeChange {
+            adjustment: if gain < 0.0 { -adjustment } else { adjustment },
+            peak: 0,
+        }
+    }
+}
+
+/// The identification that RVAD frames are assumed to have when converting them to RVA2 frames.
+const RVAD_IDENTIFICATION: &str = "track";
+
+impl<'a> From<&'a VolumeAdjustment> for RelativeVolumeAdjustment {
+    /// Converts an RVAD frame to an RVA2 frame with the identification "track". If all channels
+    /// are adjusted by the same amount, the master volume is adjusted instead.
+    fn from(rvad: &'a VolumeAdjustment) -> Self {
+        let peak_bytes = usize::from(rvad.bits.min(64)).div_ceil(8);
+        let channel = |channel_type, change: &VolumeChange| {
+            let (peak_bits, peak) = if change.peak == 0 {
+                (0, Vec::new())
+            } else {
+                let bytes = change.peak.to_be_bytes();
+                (rvad.bits.min(64), bytes[8 - peak_bytes..].to_vec())
+            };
+            ChannelAdjustment {
+                channel_type,
+                volume_adjustment: ChannelAdjustment::with_gain(channel_type, change.gain())
+                    .volume_adjustment,
+                peak_bits,
+                peak,
+            }
+        };
+        let others = [
+            (ChannelType::BackRight, rvad.back_right),
+            (ChannelType::BackLeft, rvad.back_left),
+            (ChannelType::FrontCentre, rvad.centre),
+            (ChannelType::Subwoofer, rvad.bass),
+        ];
+        let channels = if rvad.right == rvad.left && others.iter().all(|(_, c)| c.is_none()) {
+            vec![channel(ChannelType::MasterVolume, &rvad.right)]
+        } else {
+            let mut channels = vec![
+                channel(ChannelType::FrontRight, &rvad.right),
+                channel(ChannelType::FrontLeft, &rvad.left),
+            ];
+            channels.extend(
+                others
+                    .iter()
+                    .filter_map(|(ty, change)| change.as_ref().map(|c| channel(*ty, c))),
+            );
+            channels
+        };
+        RelativeVolumeAdjustment {
+            identification: RVAD_IDENTIFICATION.to_string(),
+            channels,
+        }
+    }
+}
+
+impl<'a> From<&'a RelativeVolumeAdjustment> for VolumeAdjustment {
+    /// Converts an RVA2 frame to an RVAD frame using 16 bits. The master volume adjustment is
+    /// applied to the right and left channels unless they have adjustments of their own.
+    /// Channels that RVAD does not support are left out.
+    fn from(rva2: &'a RelativeVolumeAdjustment) -> Self {
+        let change = |channel_type| {
+            rva2.channel(channel_type).map(|channel| {
+                let mut change = VolumeChange::with_gain(channel.gain());
+                change.peak = channel
+                    .peak_amplitude()
+                    .map(|peak| (peak * 32768.0).round().min(f64::from(u16::MAX)) as u64)
+                    .unwrap_or(0);
+                change
+            })
+        };
+        let master = change(ChannelType::MasterVolume).unwrap_or_default();
+        VolumeAdjustment {
+            bits: 16,
+            right: change(ChannelType::FrontRight).unwrap_or(master),
+            left: change(ChannelType::FrontLeft).unwrap_or(master),
+            back_right: change(ChannelType::BackRight),
+            back_left: change(ChannelType::BackLeft),
+            centre: change(ChannelType::FrontCentre),
+            bass: change(ChannelType::Subwoofer),
+        }
+    }
+}
+
+/// The parsed contents of an event timing codes frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EventTimingCodes {
+    /// The unit of the timestamps.
+    pub timestamp_format: TimestampFormat,
+    /// The events and the timestamps at which they occur, in chronological order.
+    pub events: Vec<(u32, EventType)>,
+}
+
+/// Types of events used in ETCO frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum EventType {
+    Padding,
+    EndOfInitialSilence,
+    IntroStart,
+    MainPartStart,
+    OutroStart,
+    OutroEnd,
+    VerseStart,
+    RefrainStart,
+    InterludeStart,
+    ThemeStart,
+    VariationStart,
+    KeyChange,
+    TimeChange,
+    MomentaryUnwantedNoise,
+    SustainedNoise,
+    SustainedNoiseEnd,
+    IntroEnd,
+    MainPartEnd,
+    VerseEnd,
+    RefrainEnd,
+    ThemeEnd,
+    Profanity,
+    ProfanityEnd,
+    /// A synchronisation event without a predefined meaning, numbered from 0 to 15.
+    NotPredefinedSynch(u8),
+    AudioEnd,
+    AudioFileEnds,
+    Undefined(u8),
+}
+
+impl From<EventType> for u8 {
+    fn from(et: EventType) -> Self {
+        match et {
+            EventType::Padding => 0x00,
+            EventType::EndOfInitialSilence => 0x01,
+            EventType::IntroStart => 0x02,
+            EventType::MainPartStart => 0x03,
+            EventType::OutroStart => 0x04,
+            EventType::OutroEnd => 0x05,
+            EventType::VerseStart => 0x06,
+            EventType::RefrainStart => 0x07,
+            EventType::InterludeStart => 0x08,
+            EventType::ThemeStart => 0x09,
+            EventType::VariationStart => 0x0A,
+            EventType::KeyChange => 0x0B,
+            EventType::TimeChange => 0x0C,
+            EventType::MomentaryUnwantedNoise => 0x0D,
+            EventType::SustainedNoise => 0x0E,
+            EventType::SustainedNoiseEnd => 0x0F,
+            EventType::IntroEnd => 0x10,
+            EventType::MainPartEnd => 0x11,
+            EventType::VerseEnd => 0x12,
+            EventType::RefrainEnd => 0x13,
+            EventType::ThemeEnd => 0x14,
+            EventType::Profanity => 0x15,
+            EventType::ProfanityEnd => 0x16,
+            EventType::NotPredefinedSynch(n) => 0xE0 | (n & 0x0F),
+            EventType::AudioEnd => 0xFD,
+            EventType::AudioFileEnds => 0xFE,
+            EventType::Undefined(b) => b,
+        }
+    }
+}
+
+impl From<u8> for EventType {
+    fn from(b: u8) -> Self {
+        match b {
+            0x00 => EventType::Padding,
+            0x01 => EventType::EndOfInitialSilence,
+            0x02 => EventType::IntroStart,
+            0x03 => EventType::MainPartStart,
+            0x04 => EventType::OutroStart,
+            0x05 => EventType::OutroEnd,
+            0x06 => EventType::VerseStart,
+            0x07 => EventType::RefrainStart,
+            0x08 => EventType::InterludeStart,
+            0x09 => EventType::ThemeStart,
+            0x0A => EventType::VariationStart,
+            0x0B => EventType::KeyChange,
+            0x0C => EventType::TimeChange,
+            0x0D => EventType::MomentaryUnwantedNoise,
+            0x0E => EventType::SustainedNoise,
+            0x0F => EventType::SustainedNoiseEnd,
+            0x10 => EventType::IntroEnd,
+            0x11 => EventType::MainPartEnd,
+            0x12 => EventType::VerseEnd,
+            0x13 => EventType::RefrainEnd,
+            0x14 => EventType::ThemeEnd,
+            0x15 => EventType::Profanity,
+            0x16 => EventType::ProfanityEnd,
+            0xE0..=0xEF => EventType::NotPredefinedSynch(b & 0x0F),
+            0xFD => EventType::AudioEnd,
+            0xFE => EventType::AudioFileEnds,
+            b => EventType::Undefined(b),
+        }
+    }
+}
+
+/// The parsed contents of a synchronised tempo codes frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SynchronisedTempoCodes {
+    /// The unit of the timestamps.
+    pub timestamp_format: TimestampFormat,
+    /// The tempo in beats per minute, from 0 to 510, and the timestamps from which it applies,
+    /// in chronological order. A tempo of 0 means that the music is beat-free, a tempo of 1
+    /// denotes a single beat-stroke followed by a beat-free period.
+    pub tempo: Vec<(u32, u16)>,
+}
+
+/// The parsed contents of an MPEG location lookup table frame.
+///
+/// The table divides the audio into references that are a fixed number of MPEG frames apart.
+/// For every reference, the deviation from the expected byte offset and playing time is stored
+/// using the configured number of bits.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MpegLocationLookupTable {
+    /// The number of MPEG frames between two references.
+    pub frames_between_reference: u16,
+    /// The expected number of bytes between two references, at most 24 bits.
+    pub bytes_between_reference: u32,
+    /// The expected number of milliseconds between two references, at most 24 bits.
+    pub millis_between_reference: u32,
+    /// The number of bits used to store a byte deviation, at most 32.
+    pub bits_for_bytes: u8,
+    /// The number of bits used to store a millisecond deviation, at most 32. The sum of both
+    /// numbers of bits must be a multiple of four.
+    pub bits_for_millis: u8,
+    /// The deviations of the references.
+    pub references: Vec<MpegLocationLookupTableReference>,
+}
+
+/// A reference in an MPEG location lookup table.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MpegLocationLookupTableReference {
+    /// The deviation from the expected number of bytes since the previous reference.
+    pub deviate_bytes: u32,
+    /// The deviation from the expected number of milliseconds since the previous reference.
+    pub deviate_millis: u32,
+}
+
+/// The parsed contents of a position synchronisation frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PositionSynchronisation {
+    /// The unit of the position.
+    pub timestamp_format: TimestampFormat,
+    /// The position in the audio at which the file starts.
+    pub position: u32,
+}
+
+/// The parsed contents of a terms of use frame.
+///
+/// A tag may contain only one terms of use frame per language.
+#[derive(Clone, Debug, Eq)]
+pub struct TermsOfUse {
+    /// The ISO-639-2 language code of the text.
+    pub lang: String,
+    /// The terms of use.
+    pub text: String,
+}
+
+impl PartialEq for TermsOfUse {
+    fn eq(&self, other: &Self) -> bool {
+        self.lang == other.lang
+    }
+}
+
+impl Hash for TermsOfUse {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lang.hash(state);
+    }
+}
+
+/// The parsed contents of an ownership frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ownership {
+    /// The price paid, starting with the ISO-4217 currency code, e.g. "USD9.99".
+    pub price_paid: String,
+    /// The date of purchase in the format YYYYMMDD.
+    pub date_of_purchase: String,
+    /// The name of the seller.
+    pub seller: String,
+}
+
+/// The parsed contents of a commercial frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Commercial {
+    /// The prices, each starting with the ISO-4217 currency code and separated by a slash, e.g.
+    /// "USD9.99/EUR8.99".
+    pub price: String,
+    /// The date until which the prices are valid, in the format YYYYMMDD.
+    pub valid_until: String,
+    /// A URL at which the seller can be contacted.
+    pub contact_url: String,
+    /// How the audio is delivered when bought.
+    pub received_as: ReceivedAs,
+    /// The name of the seller.
+    pub seller: String,
+    /// A short description of the product.
+    pub description: String,
+    /// The logo of the seller.
+    pub seller_logo: Option<SellerLogo>,
+}
+
+/// The logo of the seller in a commercial frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SellerLogo {
+    /// The MIME type of the image, either "image/png" or "image/jpeg".
+    pub mime_type: String,
+    /// The image data.
+    pub data: Vec<u8>,
+}
+
+/// Ways in which the audio of a commercial frame is delivered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum ReceivedAs {
+    Other,
+    StandardCdAlbum,
+    CompressedAudioOnCd,
+    FileOverInternet,
+    StreamOverInternet,
+    NoteSheets,
+    NoteSheetsInBook,
+    MusicOnOtherMedia,
+    NonMusicalMerchandise,
+    Undefined(u8),
+}
+
+impl From<ReceivedAs> for u8 {
+    fn from(received_as: ReceivedAs) -> Self {
+        match received_as {
+            ReceivedAs::Other => 0,
+            ReceivedAs::StandardCdAlbum => 1,
+            ReceivedAs::CompressedAudioOnCd => 2,
+            ReceivedAs::FileOverInternet => 3,
+            ReceivedAs::StreamOverInternet => 4,
+            ReceivedAs::NoteSheets => 5,
+            ReceivedAs::NoteSheetsInBook => 6,
+            ReceivedAs::MusicOnOtherMedia => 7,
+            ReceivedAs::NonMusicalMerchandise => 8,
+            ReceivedAs::Undefined(b) => b,
+        }
+    }
+}
+
+impl From<u8> for ReceivedAs {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => ReceivedAs::Other,
+            1 => ReceivedAs::StandardCdAlbum,
+            2 => ReceivedAs::CompressedAudioOnCd,
+            3 => ReceivedAs::FileOverInternet,
+            4 => ReceivedAs::StreamOverInternet,
+            5 => ReceivedAs::NoteSheets,
+            6 => ReceivedAs::NoteSheetsInBook,
+            7 => ReceivedAs::MusicOnOtherMedia,
+            8 => ReceivedAs::NonMusicalMerchandise,
+            b => ReceivedAs::Undefined(b),
+        }
+    }
+}
+
+/// The parsed contents of a linked information frame.
+///
+/// A linked information frame refers to a frame in the tag of another file, which should be
+/// treated as if it were part of this tag.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LinkedInformation {
+    /// The identifier of the linked frame, e.g. "APIC".
+    pub frame_identifier: String,
+    /// The URL of the file that contains the linked frame.
+    pub url: String,
+    /// The data that identifies the linked frame if there may be more than one frame with the
+    /// identifier, e.g. the description of a picture. For comments and lyrics the description
+    /// is preceded by the three character language code.
+    pub additional_data: Vec<String>,
+}
+
+/// The parsed contents of an audio seek point index frame.
+///
+/// The index divides the indexed audio data into equally long parts in time. Every index point
+/// is the offset of the start of a part as a fraction of the length of the indexed data.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AudioSeekPointIndex {
+    /// The offset of the indexed audio data from the start of the file, which is the end of the
+    /// tag if it is prepended.
+    pub indexed_data_start: u32,
+    /// The length of the indexed audio data in bytes.
+    pub indexed_data_length: u32,
+    /// The number of bits used to store an index point, either 8 or 16.
+    pub bits_per_index_point: u8,
+    /// The index points, each of which is multiplied by the indexed data length and divided by
+    /// 2^bits to get the byte offset in the indexed data.
+    pub index_points: Vec<u16>,
+}
+
+/// The parsed contents of an ID3v2.4 equalisation frame.
+///
+/// A tag may contain only one equalisation per identification.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Equalisation {
+    /// How the adjustments between the frequencies are interpolated.
+    pub interpolation_method: InterpolationMethod,
+    /// Identifies the situation or device the equalisation is meant for.
+    pub identification: String,
+    /// The adjustments as pairs of a frequency in units of 1/2 Hz and a volume adjustment in
+    /// units of 1/512 dB, ordered by frequency.
+    pub adjustments: Vec<(u16, i16)>,
+}
+
+/// The ways in which the adjustments of an EQU2 frame are interpolated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InterpolationMethod {
+    /// The adjustment jumps to the next level halfway between two frequencies.
+    Band,
+    /// The adjustment is linearly interpolated between two frequencies.
+    Linear,
+    /// A method that is not defined by the specification.
+    Undefined(u8),
+}
+
+impl From<InterpolationMethod> for u8 {
+    fn from(method: InterpolationMethod) -> Self {
+        match method {
+            InterpolationMethod::Band => 0,
+            InterpolationMethod::Linear => 1,
+            InterpolationMethod::Undefined(b) => b,
+        }
+    }
+}
+
+impl From<u8> for InterpolationMethod {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => InterpolationMethod::Band,
+            1 => InterpolationMethod::Linear,
+            b => InterpolationMethod::Undefined(b),
+        }
+    }
+}
+
+/// The parsed contents of an ID3v2.2/ID3v2.3 equalisation frame.
+///
+/// The adjustments are interpreted in the same way as those of a `VolumeAdjustment`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LegacyEqualisation {
+    /// The number of bits used to store an adjustment, usually 16.
+    pub bits: u8,
+    /// The adjustments as pairs of a frequency in Hz, at most 32767, and a volume adjustment,
+    /// ordered by frequency.
+    pub adjustments: Vec<(u16, i64)>,
+}
+
+impl<'a> From<&'a LegacyEqualisation> for Equalisation {
+    /// Converts an EQUA frame to an EQU2 frame with an empty identification.
+    fn from(equa: &'a LegacyEqualisation) -> Self {
+        let adjustments = equa
+            .adjustments
+            .iter()
+            .map(|&(frequency, adjustment)| {
+                let gain = VolumeChange {
+                    adjustment,
+                    peak: 0,
+                }
+                .gain();
+                let adjustment = (gain * 512.0)
+                    .round()
+                    .clamp(f32::from(i16::MIN), f32::from(i16::MAX));
+                (frequency.min(0x7FFF) * 2, adjustment as i16)
+            })
+            .collect();
+        Equalisation {
+            interpolation_method: InterpolationMethod::Band,
+            identification: String::new(),
+            adjustments,
+        }
+    }
+}
+
+impl<'a> From<&'a Equalisation> for LegacyEqualisation {
+    /// Converts an EQU2 frame to an EQUA frame with 16 bit adjustments. The identification and
+    /// interpolation method are lost.
+    fn from(equ2: &'a Equalisation) -> Self {
+        let adjustments = equ2
+            .adjustments
+            .iter()
+            .map(|&(frequency, adjustment)| {
+                let change = VolumeChange::with_gain(f32::from(adjustment) / 512.0);
+                (frequency / 2, change.adjustment.clamp(-0xFFFF, 0xFFFF))
+            })
+            .collect();
+        LegacyEqualisation {
+            bits: 16,
+            adjustments,
+        }
+    }
+}
+
+/// The parsed contents of a reverb frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Reverb {
+    /// The delay between the bounces of the left channel in milliseconds.
+    pub reverb_left: u16,
+    /// The delay between the bounces of the right channel in milliseconds.
+    pub reverb_right: u16,
+    /// The number of bounces of the left channel, 0xFF means infinite.
+    pub bounces_left: u8,
+    /// The number of bounces of the right channel, 0xFF means infinite.
+    pub bounces_right: u8,
+    /// The part of the left channel that is bounced back to the left channel, from 0 to 0xFF.
+    pub feedback_left_to_left: u8,
+    /// The part of the left channel that is bounced to the right channel, from 0 to 0xFF.
+    pub feedback_left_to_right: u8,
+    /// The part of the right channel that is bounced back to the right channel, from 0 to 0xFF.
+    pub feedback_right_to_right: u8,
+    /// The part of the right channel that is bounced to the left channel, from 0 to 0xFF.
+    pub feedback_right_to_left: u8,
+    /// The part of the left channel that is mixed into the right reverb, from 0 to 0xFF.
+    pub premix_left_to_right: u8,
+    /// The part of the right channel that is mixed into the left reverb, from 0 to 0xFF.
+    pub premix_right_to_left: u8,
+}
+
+/// The parsed contents of a recommended buffer size frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecommendedBufferSize {
+    /// The recommended buffer size in bytes, at most 24 bits.
+    pub buffer_size: u32,
+    /// Whether the audio stream may contain ID3 tags with information that should be applied
+    /// while playing.
+    pub embedded_info: bool,
+    /// The offset in bytes from the end of this tag to the next tag in the audio stream.
+    pub offset_to_next_tag: Option<u32>,
+}
+
+/// The conventions used by popular players to map popularimeter ratings to stars.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RatingScale {
+    /// Windows Media Player, which writes 1, 64, 128, 196 and 255.
+    WindowsMediaPlayer,
+    /// foobar2000, which writes multiples of 51.
+    Foobar2000,
+    /// MediaMonkey, which writes the same values as Windows Media Player but also uses
+    /// intermediate values for half stars.
+    MediaMonkey,
+}
+
+impl RatingScale {
+    /// Converts a popularimeter rating to a number of stars from 0 to 5. Unrated files have 0
+    /// stars.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::frame::RatingScale;
+    ///
+    /// assert_eq!(RatingScale::WindowsMediaPlayer.to_stars(196), 4);
+    /// assert_eq!(RatingScale::Foobar2000.to_stars(153), 3);
+    /// assert_eq!(RatingScale::MediaMonkey.to_stars(242), 5);
+    /// ```
+    pub fn to_stars(self, rating: u8) -> u8 {
+        match self {
+            RatingScale::WindowsMediaPlayer => match rating {
+                0 => 0,
+                1..=31 => 1,
+                32..=95 => 2,
+                96..=159 => 3,
+                160..=223 => 4,
+                _ => 5,
+            },
+            RatingScale::Foobar2000 => match rating {
+                0 => 0,
+                _ => ((u16::from(rating) + 25) / 51).max(1) as u8,
+            },
+            // Half stars are rounded up.
+            RatingScale::MediaMonkey => match rating {
+                0 => 0,
+                1..=53 => 1,
+                54..=117 => 2,
+                118..=185 => 3,
+                186..=241 => 4,
+                _ => 5,
+            },
+        }
+    }
+
+    /// Converts a number of stars from 0 to 5 to a popularimeter rating. Numbers above 5 are
+    /// treated as 5.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::frame::RatingScale;
+    ///
+    /// assert_eq!(RatingScale::WindowsMediaPlayer.to_rating(4), 196);
+    /// assert_eq!(RatingScale::Foobar2000.to_rating(3), 153);
+    /// assert_eq!(RatingScale::MediaMonkey.to_rating(0), 0);
+    /// ```
+    pub fn to_rating(self, stars: u8) -> u8 {
+        match self {
+            RatingScale::WindowsMediaPlayer | RatingScale::MediaMonkey => {
+                [0, 1, 64, 128, 196, 255][usize::from(stars.min(5))]
+            }
+            RatingScale::Foobar2000 => stars.min(5) * 51,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
 pub enum TimestampFormat {
     // Absolute time, using MPEG frames as unit.
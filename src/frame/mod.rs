@@ -4,12 +4,21 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::str;
 
+pub use self::cipher::{Ciphers, FrameCipher};
 pub use self::content::{
-    Comment, Content, ExtendedLink, ExtendedText, Lyrics, Picture, PictureType, SynchronisedLyrics,
-    SynchronisedLyricsType, TimestampFormat,
+    AudioSeekPointIndex, CdTrack, ChannelAdjustment, ChannelType, Chapter, Comment, Commercial,
+    Content, EncapsulatedObject, EncryptionMethodRegistration, Equalisation, EventTimingCodes,
+    EventType, ExtendedLink, ExtendedText, GroupIdentificationRegistration, InterpolationMethod,
+    LegacyEqualisation, LinkedInformation, Lyrics, MpegLocationLookupTable,
+    MpegLocationLookupTableReference, MusicCdIdentifier, Ownership, Picture, PictureType,
+    Popularimeter, PositionSynchronisation, Private, RatingScale, ReceivedAs,
+    RecommendedBufferSize, RelativeVolumeAdjustment, Reverb, SellerLogo, SynchronisedLyrics,
+    SynchronisedLyricsType, SynchronisedTempoCodes, TableOfContents, TermsOfUse, TimestampFormat,
+    UniqueFileIdentifier, VolumeAdjustment, VolumeChange,
 };
 pub use self::timestamp::Timestamp;
 
+mod cipher;
 mod content;
 mod timestamp;
 
@@ -22,6 +31,32 @@ enum ID {
     Invalid(String),
 }
 
+/// The flags in the header of a frame.
+///
+/// Only the preservation and read-only flags can be set directly. The grouping identity and
+/// encryption flags follow the group and encryption method of the frame. The remaining flags
+/// describe how the frame was stored when it was read, the `Encoder` decides whether to apply
+/// compression and unsynchronisation when writing.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FrameFlags {
+    /// The frame should be discarded if the tag is altered and the frame is unknown.
+    pub tag_alter_preservation: bool,
+    /// The frame should be discarded if the file is altered and the frame is unknown.
+    pub file_alter_preservation: bool,
+    /// The contents of the frame are intended to be read only.
+    pub read_only: bool,
+    /// The frame belongs to a group.
+    pub grouping_identity: bool,
+    /// The frame is compressed.
+    pub compression: bool,
+    /// The frame is encrypted.
+    pub encryption: bool,
+    /// Unsynchronisation was applied to the frame. ID3v2.4 only.
+    pub unsynchronisation: bool,
+    /// The frame contains a data length indicator. ID3v2.4 only.
+    pub data_length_indicator: bool,
+}
+
 /// A structure representing an ID3 frame.
 ///
 /// It is imporant to note that the (Partial)Eq and Hash implementations are based on the ID3 spec.
@@ -31,14 +66,56 @@ enum ID {
 pub struct Frame {
     id: ID,
     content: Content,
-    tag_alter_preservation: bool,
-    file_alter_preservation: bool,
+    flags: FrameFlags,
+    group_id: Option<u8>,
+    encryption_method: Option<u8>,
+    /// The size of the frame data before compression and encryption, as read from the frame
+    /// header. This is required to write frames that could not be decrypted.
+    data_length: Option<u32>,
+    /// The content is the encrypted data of a frame that could not be decrypted.
+    encrypted_content: bool,
 }
 
 impl PartialEq for Frame {
     fn eq(&self, other: &Frame) -> bool {
         match self.content {
-            Content::Text(_) => self.id == other.id,
+            Content::Text(_)
+            | Content::PlayCounter(_)
+            | Content::MusicCdIdentifier(_)
+            | Content::VolumeAdjustment(_)
+            | Content::EventTimingCodes(_)
+            | Content::SynchronisedTempoCodes(_)
+            | Content::MpegLocationLookupTable(_)
+            | Content::PositionSynchronisation(_)
+            | Content::Ownership(_)
+            | Content::AudioSeekPointIndex(_)
+            | Content::SeekOffset(_)
+            | Content::LegacyEqualisation(_)
+            | Content::Reverb(_)
+            | Content::RecommendedBufferSize(_) => self.id == other.id,
+            // These frames are unique by their element ID or identification.
+            Content::Chapter(ref chapter) => {
+                self.id == other.id
+                    && other.content.chapter().map(|c| &c.element_id) == Some(&chapter.element_id)
+            }
+            Content::TableOfContents(ref toc) => {
+                self.id == other.id
+                    && other.content.table_of_contents().map(|t| &t.element_id)
+                        == Some(&toc.element_id)
+            }
+            Content::RelativeVolumeAdjustment(ref rva2) => {
+                self.id == other.id
+                    && other
+                        .content
+                        .relative_volume_adjustment()
+                        .map(|r| &r.identification)
+                        == Some(&rva2.identification)
+            }
+            Content::Equalisation(ref equ2) => {
+                self.id == other.id
+                    && other.content.equalisation().map(|e| &e.identification)
+                        == Some(&equ2.identification)
+            }
             _ => self.id == other.id && self.content == other.content,
         }
     }
@@ -47,7 +124,36 @@ impl PartialEq for Frame {
 impl Hash for Frame {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match self.content {
-            Content::Text(_) => self.id.hash(state),
+            Content::Text(_)
+            | Content::PlayCounter(_)
+            | Content::MusicCdIdentifier(_)
+            | Content::VolumeAdjustment(_)
+            | Content::EventTimingCodes(_)
+            | Content::SynchronisedTempoCodes(_)
+            | Content::MpegLocationLookupTable(_)
+            | Content::PositionSynchronisation(_)
+            | Content::Ownership(_)
+            | Content::AudioSeekPointIndex(_)
+            | Content::SeekOffset(_)
+            | Content::LegacyEqualisation(_)
+            | Content::Reverb(_)
+            | Content::RecommendedBufferSize(_) => self.id.hash(state),
+            Content::Chapter(ref chapter) => {
+                self.id.hash(state);
+                chapter.element_id.hash(state);
+            }
+            Content::TableOfContents(ref toc) => {
+                self.id.hash(state);
+                toc.element_id.hash(state);
+            }
+            Content::RelativeVolumeAdjustment(ref rva2) => {
+                self.id.hash(state);
+                rva2.identification.hash(state);
+            }
+            Content::Equalisation(ref equ2) => {
+                self.id.hash(state);
+                equ2.identification.hash(state);
+            }
             _ => {
                 self.id.hash(state);
                 self.content.hash(state);
@@ -79,8 +185,11 @@ impl Frame {
                 ID::Valid(id.as_ref().to_string())
             },
             content,
-            tag_alter_preservation: false,
-            file_alter_preservation: false,
+            flags: FrameFlags::default(),
+            group_id: None,
+            encryption_method: None,
+            data_length: None,
+            encrypted_content: false,
         }
     }
 
@@ -111,24 +220,115 @@ impl Frame {
         &self.content
     }
 
+    /// Returns a copy of the frame with a different ID and content, retaining the flags.
+    pub(crate) fn with_converted_content(&self, id: &str, content: Content) -> Frame {
+        Frame {
+            id: ID::Valid(id.to_string()),
+            content,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a mutable reference to the content of the frame.
+    pub(crate) fn content_mut(&mut self) -> &mut Content {
+        &mut self.content
+    }
+
+    /// Returns the flags of the frame header.
+    pub fn flags(&self) -> FrameFlags {
+        self.flags
+    }
+
+    /// Sets the flags that describe how the frame was stored.
+    pub(crate) fn set_decoded_flags(&mut self, flags: FrameFlags, data_length: Option<u32>) {
+        self.flags = FrameFlags {
+            grouping_identity: self.group_id.is_some(),
+            encryption: self.encryption_method.is_some(),
+            ..flags
+        };
+        self.data_length = data_length;
+    }
+
+    /// Returns the size of the frame data before compression and encryption if it was stored
+    /// in the frame header.
+    pub(crate) fn data_length(&self) -> Option<u32> {
+        self.data_length
+    }
+
+    /// Marks the content of the frame as the encrypted data of a frame that could not be
+    /// decrypted.
+    pub(crate) fn set_encrypted_content(&mut self) {
+        self.encrypted_content = true;
+    }
+
+    /// Returns whether the content of the frame is the encrypted data of a frame that could not
+    /// be decrypted because no cipher was registered for its method. Such frames are written back
+    /// as is, along with their encryption method.
+    pub fn has_encrypted_content(&self) -> bool {
+        self.encrypted_content
+    }
+
     /// Returns whether the tag_alter_preservation flag is set.
     pub fn tag_alter_preservation(&self) -> bool {
-        self.tag_alter_preservation
+        self.flags.tag_alter_preservation
     }
 
     /// Sets the tag_alter_preservation flag.
     pub fn set_tag_alter_preservation(&mut self, tag_alter_preservation: bool) {
-        self.tag_alter_preservation = tag_alter_preservation;
+        self.flags.tag_alter_preservation = tag_alter_preservation;
     }
 
     /// Returns whether the file_alter_preservation flag is set.
     pub fn file_alter_preservation(&self) -> bool {
-        self.file_alter_preservation
+        self.flags.file_alter_preservation
     }
 
     /// Sets the file_alter_preservation flag.
     pub fn set_file_alter_preservation(&mut self, file_alter_preservation: bool) {
-        self.file_alter_preservation = file_alter_preservation;
+        self.flags.file_alter_preservation = file_alter_preservation;
+    }
+
+    /// Returns whether the read_only flag is set.
+    pub fn read_only(&self) -> bool {
+        self.flags.read_only
+    }
+
+    /// Sets the read_only flag.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.flags.read_only = read_only;
+    }
+
+    /// Returns the symbol of the group this frame belongs to.
+    ///
+    /// The group is described by the GRID frame with the same group symbol.
+    pub fn group_id(&self) -> Option<u8> {
+        self.group_id
+    }
+
+    /// Sets the symbol of the group this frame belongs to.
+    ///
+    /// Groups are not supported by ID3v2.2, the group is not written for that version.
+    pub fn set_group_id(&mut self, group_id: Option<u8>) {
+        self.group_id = group_id;
+        self.flags.grouping_identity = group_id.is_some();
+    }
+
+    /// Returns the symbol of the method this frame is encrypted with.
+    ///
+    /// The method is described by the ENCR frame with the same method symbol. If a frame could not
+    /// be decrypted because no cipher was registered for its method, its content is the encrypted
+    /// data as `Content::Unknown`, see `Frame::has_encrypted_content`.
+    pub fn encryption_method(&self) -> Option<u8> {
+        self.encryption_method
+    }
+
+    /// Sets the symbol of the method this frame should be encrypted with. A cipher for the method
+    /// must be registered with the `Encoder` when the frame is written.
+    ///
+    /// Encryption is not supported by ID3v2.2, the frame is written unencrypted for that version.
+    pub fn set_encryption_method(&mut self, encryption_method: Option<u8>) {
+        self.encryption_method = encryption_method;
+        self.flags.encryption = encryption_method.is_some();
     }
 }
 
@@ -150,6 +350,94 @@ impl fmt::Display for Frame {
                 "{}: {:?} ({:?})",
                 content.description, content.picture_type, content.mime_type
             ),
+            Content::GroupIdentificationRegistration(ref content) => write!(
+                f,
+                "{}: {:#04x}",
+                content.owner_identifier, content.group_symbol
+            ),
+            Content::EncryptionMethodRegistration(ref content) => write!(
+                f,
+                "{}: {:#04x}",
+                content.owner_identifier, content.method_symbol
+            ),
+            Content::UniqueFileIdentifier(ref content) => write!(
+                f,
+                "{}: {}",
+                content.owner_identifier,
+                String::from_utf8_lossy(&content.identifier)
+            ),
+            Content::PlayCounter(counter) => write!(f, "{}", counter),
+            Content::EventTimingCodes(ref content) => {
+                write!(f, "{} events", content.events.len())
+            }
+            Content::SynchronisedTempoCodes(ref content) => {
+                write!(f, "{} tempo changes", content.tempo.len())
+            }
+            Content::MpegLocationLookupTable(ref content) => {
+                write!(f, "{} references", content.references.len())
+            }
+            Content::PositionSynchronisation(ref content) => write!(f, "{}", content.position),
+            Content::TermsOfUse(ref content) => write!(f, "{}", content.text),
+            Content::Ownership(ref content) => {
+                write!(f, "{} ({})", content.seller, content.price_paid)
+            }
+            Content::AudioSeekPointIndex(ref content) => {
+                write!(f, "{} index points", content.index_points.len())
+            }
+            Content::SeekOffset(offset) => write!(f, "{}", offset),
+            Content::Equalisation(ref content) => write!(f, "{}", content.identification),
+            Content::LegacyEqualisation(ref content) => {
+                write!(f, "{} adjustments", content.adjustments.len())
+            }
+            Content::Reverb(ref content) => {
+                write!(f, "{} ms/{} ms", content.reverb_left, content.reverb_right)
+            }
+            Content::RecommendedBufferSize(ref content) => write!(f, "{}", content.buffer_size),
+            Content::LinkedInformation(ref content) => {
+                write!(f, "{} ({})", content.frame_identifier, content.url)
+            }
+            Content::Commercial(ref content) => {
+                write!(f, "{} ({})", content.description, content.price)
+            }
+            Content::RelativeVolumeAdjustment(ref content) => write!(
+                f,
+                "{}: {} channels",
+                content.identification,
+                content.channels.len()
+            ),
+            Content::VolumeAdjustment(ref content) => write!(
+                f,
+                "{:.2} dB, {:.2} dB",
+                content.right.gain(),
+                content.left.gain()
+            ),
+            Content::MusicCdIdentifier(ref content) => {
+                write!(f, "tracks {}-{}", content.first_track, content.last_track)
+            }
+            Content::Chapter(ref content) => write!(
+                f,
+                "{}: {}-{} ms",
+                content.element_id, content.start_time, content.end_time
+            ),
+            Content::TableOfContents(ref content) => {
+                write!(f, "{}: {}", content.element_id, content.elements.join(", "))
+            }
+            Content::Private(ref content) => write!(
+                f,
+                "{}: {} bytes",
+                content.owner_identifier,
+                content.data.len()
+            ),
+            Content::EncapsulatedObject(ref content) => write!(
+                f,
+                "{}: {} ({:?})",
+                content.description, content.filename, content.mime_type
+            ),
+            Content::Popularimeter(ref content) => write!(
+                f,
+                "{}: {} ({})",
+                content.user, content.rating, content.counter
+            ),
             Content::Unknown(ref content) => write!(f, "unknown, {} bytes", content.len()),
         }
     }
@@ -173,4 +461,75 @@ mod tests {
         );
         assert_eq!(format!("{}", txxx_frame), "description: value");
     }
+
+    #[test]
+    fn test_eq_unique_by_identification() {
+        let rva2 = |identification: &str, volume_adjustment| {
+            Content::RelativeVolumeAdjustment(RelativeVolumeAdjustment {
+                identification: identification.to_string(),
+                channels: vec![ChannelAdjustment {
+                    channel_type: ChannelType::MasterVolume,
+                    volume_adjustment,
+                    peak_bits: 0,
+                    peak: Vec::new(),
+                }],
+            })
+        };
+        assert_ne!(rva2("track", 1), rva2("track", 2));
+        assert_eq!(
+            Frame::with_content("RVA2", rva2("track", 1)),
+            Frame::with_content("RVA2", rva2("track", 2))
+        );
+        assert_ne!(
+            Frame::with_content("RVA2", rva2("track", 1)),
+            Frame::with_content("RVA2", rva2("album", 1))
+        );
+
+        let equ2 = |identification: &str, adjustments| {
+            Content::Equalisation(Equalisation {
+                interpolation_method: InterpolationMethod::Linear,
+                identification: identification.to_string(),
+                adjustments,
+            })
+        };
+        assert_ne!(equ2("", vec![(200, 1)]), equ2("", Vec::new()));
+        assert_eq!(
+            Frame::with_content("EQU2", equ2("", vec![(200, 1)])),
+            Frame::with_content("EQU2", equ2("", Vec::new()))
+        );
+        assert_ne!(
+            Frame::with_content("EQU2", equ2("", Vec::new())),
+            Frame::with_content("EQU2", equ2("other", Vec::new()))
+        );
+    }
+
+    #[test]
+    fn test_eq_embedded_frames() {
+        let chapter = |title: &str| Chapter {
+            element_id: "ch0".to_string(),
+            start_time: 0,
+            end_time: 60_000,
+            start_offset: None,
+            end_offset: None,
+            frames: vec![Frame::with_content(
+                "TIT2",
+                Content::Text(title.to_string()),
+            )],
+        };
+        assert_eq!(chapter("Intro"), chapter("Intro"));
+        assert_ne!(chapter("Intro"), chapter("Outro"));
+
+        let toc = |title: &str| TableOfContents {
+            element_id: "toc".to_string(),
+            top_level: true,
+            ordered: true,
+            elements: vec!["ch0".to_string()],
+            frames: vec![Frame::with_content(
+                "TIT2",
+                Content::Text(title.to_string()),
+            )],
+        };
+        assert_eq!(toc("Chapters"), toc("Chapters"));
+        assert_ne!(toc("Chapters"), toc("Other"));
+    }
 }
@@ -48,10 +48,13 @@
 extern crate derive_builder;
 
 pub use crate::error::{Error, ErrorKind, Result};
+pub use crate::extended_header::ExtendedHeader;
 pub use crate::frame::{Content, Frame, Timestamp};
 pub use crate::stream::tag::{Encoder, EncoderBuilder};
-pub use crate::tag::{Tag, Version};
+pub use crate::tag::{MergePolicy, ReplayGain, ReplayGainFormat, Tag, TagSource, Version};
 
+/// Contains types describing the extended header of ID3v2 tags.
+pub mod extended_header;
 /// Contains types and methods for operating on ID3 frames.
 pub mod frame;
 /// Utilities for working with ID3v1 tags.
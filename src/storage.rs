@@ -346,6 +346,59 @@ pub fn locate_id3v2(
     Ok(Some(0..u64::from(size) + num_padding as u64))
 }
 
+/// Returns the offset at which the ID3v1 tag, including its TAG+ extension, starts. If the
+/// reader does not contain an ID3v1 tag, the length of the reader is returned.
+pub fn locate_id3v1(mut reader: impl io::Read + io::Seek) -> crate::Result<u64> {
+    let mut end = reader.seek(io::SeekFrom::End(0))?;
+    for &(size, magic) in &[(128, &b"TAG"[..]), (227, &b"TAG+"[..])] {
+        if end < size {
+            break;
+        }
+        let mut buf = [0; 4];
+        reader.seek(io::SeekFrom::Start(end - size))?;
+        reader.read_exact(&mut buf[..magic.len()])?;
+        if &buf[..magic.len()] != magic {
+            break;
+        }
+        end -= size;
+    }
+    Ok(end)
+}
+
+/// Locates an ID3v2.4 tag that has been appended to the end of the file by looking for its footer
+/// right before the ID3v1 tag or the end of the file.
+///
+/// The returned range includes the header and the footer of the tag. Data that only resembles a
+/// footer, because it is not followed by a matching header, is not considered to be a tag.
+pub fn locate_id3v2_appended(
+    mut reader: impl io::Read + io::Seek,
+) -> crate::Result<Option<ops::Range<u64>>> {
+    let end = locate_id3v1(&mut reader)?;
+    if end < 20 {
+        return Ok(None);
+    }
+    let mut footer = [0u8; 10];
+    reader.seek(io::SeekFrom::Start(end - 10))?;
+    reader.read_exact(&mut footer)?;
+    // Only ID3v2.4 tags have a footer.
+    if &footer[..4] != b"3DI\x04" {
+        return Ok(None);
+    }
+
+    let size = u64::from(unsynch::decode_u32(BigEndian::read_u32(&footer[6..10])));
+    if end < size + 20 {
+        return Ok(None);
+    }
+    let start = end - size - 20;
+    let mut header = [0u8; 10];
+    reader.seek(io::SeekFrom::Start(start))?;
+    reader.read_exact(&mut header)?;
+    if &header[..3] != b"ID3" || header[3..] != footer[3..] {
+        return Ok(None);
+    }
+    Ok(Some(start..end))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -522,4 +575,49 @@ mod tests {
         let location = locate_id3v2(file).unwrap();
         assert!(location.is_some());
     }
+
+    #[test]
+    fn test_locate_id3v2_appended() {
+        let mut tag = vec![0xff; 4];
+        tag.extend(b"ID3\x04\x00\x10\x00\x00\x00\x04");
+        tag.extend(&[0; 4]);
+        tag.extend(b"3DI\x04\x00\x10\x00\x00\x00\x04");
+        assert_eq!(
+            Some(4..28),
+            locate_id3v2_appended(io::Cursor::new(&tag)).unwrap()
+        );
+
+        let mut v1 = tag.clone();
+        v1.extend(b"TAG+");
+        v1.extend(&[0; 223]);
+        v1.extend(b"TAG");
+        v1.extend(&[0; 125]);
+        assert_eq!(28, locate_id3v1(io::Cursor::new(&v1)).unwrap());
+        assert_eq!(
+            Some(4..28),
+            locate_id3v2_appended(io::Cursor::new(&v1)).unwrap()
+        );
+
+        assert_eq!(
+            None,
+            locate_id3v2_appended(io::Cursor::new(&tag[..27])).unwrap()
+        );
+
+        // Data that resembles a footer without a matching header is not a tag.
+        let mut mismatch = tag.clone();
+        mismatch[10] = 0xff;
+        assert_eq!(
+            None,
+            locate_id3v2_appended(io::Cursor::new(&mismatch)).unwrap()
+        );
+        let mut version = tag.clone();
+        version[21] = 3;
+        assert_eq!(
+            None,
+            locate_id3v2_appended(io::Cursor::new(&version)).unwrap()
+        );
+        let mut size = tag.clone();
+        size[27] = 0x7f;
+        assert_eq!(None, locate_id3v2_appended(io::Cursor::new(&size)).unwrap());
+    }
 }
@@ -1,14 +1,21 @@
 use crate::frame::{
-    Content, ExtendedLink, Picture, PictureType, SynchronisedLyrics, SynchronisedLyricsType,
-    TimestampFormat,
+    AudioSeekPointIndex, CdTrack, ChannelAdjustment, Chapter, Ciphers, Commercial, Content,
+    EncryptionMethodRegistration, Equalisation, EventTimingCodes, EventType, ExtendedLink, Frame,
+    GroupIdentificationRegistration, LegacyEqualisation, LinkedInformation,
+    MpegLocationLookupTable, MpegLocationLookupTableReference, MusicCdIdentifier, Ownership,
+    Picture, PictureType, Popularimeter, PositionSynchronisation, Private, RecommendedBufferSize,
+    RelativeVolumeAdjustment, Reverb, SellerLogo, SynchronisedLyrics, SynchronisedLyricsType,
+    SynchronisedTempoCodes, TableOfContents, TimestampFormat, UniqueFileIdentifier,
+    VolumeAdjustment, VolumeChange,
 };
 use crate::stream::encoding::Encoding;
 use crate::tag;
 use crate::util::{
-    delim_len, string_from_latin1, string_from_utf16, string_from_utf16be, string_to_latin1,
-    string_to_utf16, string_to_utf16be,
+    convert_id_2_to_3, convert_id_3_to_2, delim_len, string_from_latin1, string_from_utf16,
+    string_from_utf16be, string_to_latin1, string_to_utf16, string_to_utf16be,
 };
 use crate::{Error, ErrorKind};
+use byteorder::{BigEndian, ByteOrder, WriteBytesExt};
 use std::io;
 use std::iter;
 
@@ -40,6 +47,32 @@ pub fn encode(
         Content::SynchronisedLyrics(_) => synchronised_lyrics_to_bytes(request),
         Content::Comment(_) => comment_to_bytes(request),
         Content::Picture(_) => picture_to_bytes(request)?,
+        Content::GroupIdentificationRegistration(_) => grid_to_bytes(request),
+        Content::EncryptionMethodRegistration(_) => encr_to_bytes(request),
+        Content::UniqueFileIdentifier(_) => ufid_to_bytes(request),
+        Content::PlayCounter(_) => pcnt_to_bytes(request),
+        Content::Popularimeter(_) => popm_to_bytes(request),
+        Content::EncapsulatedObject(_) => geob_to_bytes(request),
+        Content::Private(_) => priv_to_bytes(request),
+        Content::Chapter(_) => chap_to_bytes(request)?,
+        Content::TableOfContents(_) => ctoc_to_bytes(request)?,
+        Content::MusicCdIdentifier(_) => mcdi_to_bytes(request)?,
+        Content::RelativeVolumeAdjustment(_) => rva2_to_bytes(request),
+        Content::VolumeAdjustment(_) => rvad_to_bytes(request)?,
+        Content::EventTimingCodes(_) => etco_to_bytes(request),
+        Content::SynchronisedTempoCodes(_) => sytc_to_bytes(request),
+        Content::MpegLocationLookupTable(_) => mllt_to_bytes(request)?,
+        Content::PositionSynchronisation(_) => poss_to_bytes(request),
+        Content::TermsOfUse(_) => user_to_bytes(request),
+        Content::Ownership(_) => owne_to_bytes(request)?,
+        Content::Commercial(_) => comr_to_bytes(request)?,
+        Content::LinkedInformation(_) => link_to_bytes(request)?,
+        Content::AudioSeekPointIndex(_) => aspi_to_bytes(request)?,
+        Content::SeekOffset(offset) => offset.to_be_bytes().to_vec(),
+        Content::Equalisation(_) => equ2_to_bytes(request),
+        Content::LegacyEqualisation(_) => equa_to_bytes(request)?,
+        Content::Reverb(_) => rvrb_to_bytes(request),
+        Content::RecommendedBufferSize(_) => rbuf_to_bytes(request)?,
         Content::Unknown(data) => data.clone(),
     };
     writer.write_all(&bytes)?;
@@ -47,7 +80,14 @@ pub fn encode(
 }
 
 /// Attempts to decode the request.
-pub fn decode(id: &str, mut reader: impl io::Read) -> crate::Result<Content> {
+///
+/// The version is required to decode the frames that are embedded in chapter and table of
+/// contents frames.
+pub fn decode(
+    id: &str,
+    version: tag::Version,
+    mut reader: impl io::Read,
+) -> crate::Result<Content> {
     let mut data = Vec::new();
     reader.read_to_end(&mut data)?;
     match id {
@@ -58,6 +98,32 @@ pub fn decode(id: &str, mut reader: impl io::Read) -> crate::Result<Content> {
         "COMM" | "COM" => parse_comm(data.as_slice()),
         "USLT" | "ULT" => parse_uslt(data.as_slice()),
         "SYLT" | "SLT" => parse_sylt(data.as_slice()),
+        "GRID" => parse_grid(data.as_slice()),
+        "ENCR" => parse_encr(data.as_slice()),
+        "UFID" | "UFI" => parse_ufid(data.as_slice()),
+        "PCNT" | "CNT" => parse_pcnt(data),
+        "POPM" | "POP" => parse_popm(data),
+        "GEOB" | "GEO" => parse_geob(data.as_slice()),
+        "PRIV" => parse_priv(data.as_slice()),
+        "CHAP" => parse_chap(data.as_slice(), version),
+        "CTOC" => parse_ctoc(data.as_slice(), version),
+        "MCDI" | "MCI" => parse_mcdi(data),
+        "RVA2" => parse_rva2(data.as_slice()),
+        "RVAD" | "RVA" => parse_rvad(data),
+        "ETCO" | "ETC" => parse_etco(data.as_slice()),
+        "SYTC" | "STC" => parse_sytc(data.as_slice()),
+        "MLLT" | "MLL" => parse_mllt(data.as_slice()),
+        "POSS" => parse_poss(data.as_slice()),
+        "USER" => parse_user(data.as_slice()),
+        "OWNE" => parse_owne(data.as_slice()),
+        "COMR" => parse_comr(data.as_slice()),
+        "LINK" | "LNK" => parse_link(data.as_slice(), version),
+        "ASPI" => parse_aspi(data.as_slice()),
+        "SEEK" => parse_seek(data.as_slice()),
+        "EQU2" => parse_equ2(data.as_slice()),
+        "EQUA" | "EQU" => parse_equa(data),
+        "RVRB" | "REV" => parse_rvrb(data.as_slice()),
+        "RBUF" | "BUF" => parse_rbuf(data.as_slice()),
         id if id.starts_with('T') => parse_text(data.as_slice()),
         id if id.starts_with('W') => parse_weblink(data.as_slice()),
         _ => Ok(Content::Unknown(data)),
@@ -306,6 +372,514 @@ fn picture_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
     }
 }
 
+fn geob_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.encapsulated_object().unwrap();
+    encode!(
+        encoding(request.encoding),
+        bytes(content.mime_type.as_bytes()),
+        byte(0),
+        string(content.filename),
+        delim(0),
+        string(content.description),
+        delim(0),
+        bytes(content.data)
+    )
+}
+
+fn grid_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.group_identification_registration().unwrap();
+    let mut buf = string_to_latin1(&content.owner_identifier);
+    buf.push(0);
+    buf.push(content.group_symbol);
+    buf.extend(&content.data);
+    buf
+}
+
+fn encr_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.encryption_method_registration().unwrap();
+    let mut buf = string_to_latin1(&content.owner_identifier);
+    buf.push(0);
+    buf.push(content.method_symbol);
+    buf.extend(&content.data);
+    buf
+}
+
+fn ufid_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.unique_file_identifier().unwrap();
+    let mut buf = string_to_latin1(&content.owner_identifier);
+    buf.push(0);
+    buf.extend(&content.identifier);
+    buf
+}
+
+fn priv_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.private().unwrap();
+    let mut buf = string_to_latin1(&content.owner_identifier);
+    buf.push(0);
+    buf.extend(&content.data);
+    buf
+}
+
+fn user_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.terms_of_use().unwrap();
+    encode!(
+        encoding(request.encoding),
+        bytes(
+            content
+                .lang
+                .bytes()
+                .chain(iter::repeat(b' '))
+                .take(3)
+                .collect::<Vec<u8>>()
+        ),
+        string(content.text)
+    )
+}
+
+/// Checks that a date of an ownership or commercial frame is in the format YYYYMMDD.
+fn date_to_bytes(date: &str) -> crate::Result<&[u8]> {
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "date is not in the format YYYYMMDD",
+        ));
+    }
+    Ok(date.as_bytes())
+}
+
+fn owne_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.ownership().unwrap();
+    Ok(encode!(
+        encoding(request.encoding),
+        bytes(string_to_latin1(&content.price_paid)),
+        byte(0),
+        bytes(date_to_bytes(&content.date_of_purchase)?),
+        string(content.seller)
+    ))
+}
+
+fn comr_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.commercial().unwrap();
+    let mut buf = encode!(
+        encoding(request.encoding),
+        bytes(string_to_latin1(&content.price)),
+        byte(0),
+        bytes(date_to_bytes(&content.valid_until)?),
+        bytes(string_to_latin1(&content.contact_url)),
+        byte(0),
+        byte(u8::from(content.received_as)),
+        string(content.seller),
+        delim(0),
+        string(content.description),
+        delim(0)
+    );
+    if let Some(ref logo) = content.seller_logo {
+        buf.extend(string_to_latin1(&logo.mime_type));
+        buf.push(0);
+        buf.extend(&logo.data);
+    }
+    Ok(buf)
+}
+
+fn link_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.linked_information().unwrap();
+    let id = match request.version {
+        tag::Id3v22 => {
+            convert_id_3_to_2(&content.frame_identifier).unwrap_or(&content.frame_identifier[..])
+        }
+        tag::Id3v23 | tag::Id3v24 => &content.frame_identifier[..],
+    };
+    let id_len = if request.version == tag::Id3v22 { 3 } else { 4 };
+    if id.len() != id_len {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "linked frame identifier is not valid for the tag version",
+        ));
+    }
+    let mut buf = id.as_bytes().to_vec();
+    buf.extend(string_to_latin1(&content.url));
+    buf.push(0);
+    for (i, data) in content.additional_data.iter().enumerate() {
+        if i > 0 {
+            buf.push(0);
+        }
+        buf.extend(string_to_latin1(data));
+    }
+    Ok(buf)
+}
+
+fn aspi_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.audio_seek_point_index().unwrap();
+    let num_points = content.index_points.len();
+    if num_points > 0xFFFF {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "too many index points for an audio seek point index",
+        ));
+    }
+    let mut buf = Vec::new();
+    buf.extend(&content.indexed_data_start.to_be_bytes());
+    buf.extend(&content.indexed_data_length.to_be_bytes());
+    buf.extend(&(num_points as u16).to_be_bytes());
+    buf.push(content.bits_per_index_point);
+    for &point in &content.index_points {
+        match content.bits_per_index_point {
+            8 if point <= 0xFF => buf.push(point as u8),
+            16 => buf.extend(&point.to_be_bytes()),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "index point does not fit in the number of bits per index point",
+                ))
+            }
+        }
+    }
+    Ok(buf)
+}
+
+fn equ2_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.equalisation().unwrap();
+    let mut buf = vec![content.interpolation_method.into()];
+    buf.extend(string_to_latin1(&content.identification));
+    buf.push(0);
+    for &(frequency, adjustment) in &content.adjustments {
+        buf.extend(&frequency.to_be_bytes());
+        buf.extend(&adjustment.to_be_bytes());
+    }
+    buf
+}
+
+fn equa_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.legacy_equalisation().unwrap();
+    if content.bits == 0 || content.bits > 64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "the number of bits of an equalisation adjustment must be between 1 and 64",
+        ));
+    }
+    let len = usize::from(content.bits).div_ceil(8);
+    let mut buf = vec![content.bits];
+    for &(frequency, adjustment) in &content.adjustments {
+        if frequency > 0x7FFF {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "equalisation frequency does not fit in 15 bits",
+            ));
+        }
+        let increment = if adjustment > 0 { 0x8000 } else { 0 };
+        buf.extend(&(increment | frequency).to_be_bytes());
+        buf.extend(&adjustment.unsigned_abs().to_be_bytes()[8 - len..]);
+    }
+    Ok(buf)
+}
+
+fn rvrb_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.reverb().unwrap();
+    let mut buf = Vec::with_capacity(12);
+    buf.extend(&content.reverb_left.to_be_bytes());
+    buf.extend(&content.reverb_right.to_be_bytes());
+    buf.extend(&[
+        content.bounces_left,
+        content.bounces_right,
+        content.feedback_left_to_left,
+        content.feedback_left_to_right,
+        content.feedback_right_to_right,
+        content.feedback_right_to_left,
+        content.premix_left_to_right,
+        content.premix_right_to_left,
+    ]);
+    buf
+}
+
+fn rbuf_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.recommended_buffer_size().unwrap();
+    if content.buffer_size > 0xFF_FFFF {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "recommended buffer size does not fit in 24 bits",
+        ));
+    }
+    let mut buf = content.buffer_size.to_be_bytes()[1..].to_vec();
+    buf.push(u8::from(content.embedded_info));
+    if let Some(offset) = content.offset_to_next_tag {
+        buf.extend(&offset.to_be_bytes());
+    }
+    Ok(buf)
+}
+
+/// Encodes the frames that are embedded in a chapter or table of contents frame.
+fn embedded_frames_to_bytes(
+    buf: &mut Vec<u8>,
+    frames: &[Frame],
+    version: tag::Version,
+) -> crate::Result<()> {
+    for frame in frames {
+        super::encode(buf, frame, version, false, None, &Ciphers::new())?;
+    }
+    Ok(())
+}
+
+fn chap_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.chapter().unwrap();
+    let mut buf = string_to_latin1(&content.element_id);
+    buf.push(0);
+    buf.write_u32::<BigEndian>(content.start_time)?;
+    buf.write_u32::<BigEndian>(content.end_time)?;
+    buf.write_u32::<BigEndian>(content.start_offset.unwrap_or(0xFFFF_FFFF))?;
+    buf.write_u32::<BigEndian>(content.end_offset.unwrap_or(0xFFFF_FFFF))?;
+    embedded_frames_to_bytes(&mut buf, &content.frames, request.version)?;
+    Ok(buf)
+}
+
+fn ctoc_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.table_of_contents().unwrap();
+    if content.elements.len() > 0xFF {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "a table of contents can not have more than 255 elements",
+        ));
+    }
+    let mut buf = string_to_latin1(&content.element_id);
+    buf.push(0);
+    let mut flags = 0;
+    if content.top_level {
+        flags |= 0x02;
+    }
+    if content.ordered {
+        flags |= 0x01;
+    }
+    buf.push(flags);
+    buf.push(content.elements.len() as u8);
+    for element_id in &content.elements {
+        buf.extend(string_to_latin1(element_id));
+        buf.push(0);
+    }
+    embedded_frames_to_bytes(&mut buf, &content.frames, request.version)?;
+    Ok(buf)
+}
+
+fn mcdi_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.music_cd_identifier().unwrap();
+    let mut buf = Vec::with_capacity(4 + 8 * content.tracks.len());
+    // The length excludes the length field itself.
+    buf.write_u16::<BigEndian>((2 + 8 * content.tracks.len()) as u16)?;
+    buf.push(content.first_track);
+    buf.push(content.last_track);
+    for track in &content.tracks {
+        buf.push(0);
+        buf.push(track.adr_control);
+        buf.push(track.number);
+        buf.push(0);
+        buf.write_u32::<BigEndian>(track.address)?;
+    }
+    Ok(buf)
+}
+
+fn rva2_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.relative_volume_adjustment().unwrap();
+    let mut buf = string_to_latin1(&content.identification);
+    buf.push(0);
+    for channel in &content.channels {
+        buf.push(channel.channel_type.into());
+        buf.extend(&channel.volume_adjustment.to_be_bytes());
+        buf.push(channel.peak_bits);
+        // The peak must occupy exactly as many bytes as its number of bits requires.
+        let peak_len = usize::from(channel.peak_bits).div_ceil(8);
+        let padding = peak_len.saturating_sub(channel.peak.len());
+        buf.resize(buf.len() + padding, 0);
+        buf.extend(&channel.peak[channel.peak.len().saturating_sub(peak_len)..]);
+    }
+    buf
+}
+
+/// Returns the byte that represents the timestamp format in ETCO, SYTC and POSS frames.
+fn timestamp_format_to_byte(format: TimestampFormat) -> u8 {
+    match format {
+        TimestampFormat::MPEG => 1,
+        TimestampFormat::MS => 2,
+    }
+}
+
+fn etco_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.event_timing_codes().unwrap();
+    let mut buf = vec![timestamp_format_to_byte(content.timestamp_format)];
+    for &(timestamp, event_type) in &content.events {
+        buf.push(event_type.into());
+        buf.extend(&timestamp.to_be_bytes());
+    }
+    buf
+}
+
+fn sytc_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.synchronised_tempo_codes().unwrap();
+    let mut buf = vec![timestamp_format_to_byte(content.timestamp_format)];
+    for &(timestamp, bpm) in &content.tempo {
+        // Tempos of 255 and up are stored in two bytes, the first of which is 0xFF.
+        if bpm >= 0xFF {
+            buf.push(0xFF);
+            buf.push((bpm - 0xFF).min(0xFF) as u8);
+        } else {
+            buf.push(bpm as u8);
+        }
+        buf.extend(&timestamp.to_be_bytes());
+    }
+    buf
+}
+
+fn mllt_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.mpeg_location_lookup_table().unwrap();
+    let (bits_for_bytes, bits_for_millis) = (content.bits_for_bytes, content.bits_for_millis);
+    if bits_for_bytes > 32 || bits_for_millis > 32 || (bits_for_bytes + bits_for_millis) % 4 != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "unsupported number of bits for MPEG location lookup table deviations",
+        ));
+    }
+    if content.bytes_between_reference > 0xFF_FFFF || content.millis_between_reference > 0xFF_FFFF {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "MPEG location lookup table reference distance does not fit in 24 bits",
+        ));
+    }
+    let fits = |value: u32, bits: u8| bits >= 32 || u64::from(value) < 1 << bits;
+    let mut buf = Vec::new();
+    buf.extend(&content.frames_between_reference.to_be_bytes());
+    buf.extend(&content.bytes_between_reference.to_be_bytes()[1..]);
+    buf.extend(&content.millis_between_reference.to_be_bytes()[1..]);
+    buf.push(bits_for_bytes);
+    buf.push(bits_for_millis);
+    let mut bits = BitWriter::default();
+    for reference in &content.references {
+        if !fits(reference.deviate_bytes, bits_for_bytes)
+            || !fits(reference.deviate_millis, bits_for_millis)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "MPEG location lookup table deviation does not fit in the number of bits",
+            ));
+        }
+        bits.write(reference.deviate_bytes, bits_for_bytes);
+        bits.write(reference.deviate_millis, bits_for_millis);
+    }
+    buf.extend(bits.finish());
+    Ok(buf)
+}
+
+/// Packs values of arbitrary bit widths into bytes, most significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    buf: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u8) {
+        for i in (0..bits).rev() {
+            if self.len == self.buf.len() * 8 {
+                self.buf.push(0);
+            }
+            if value >> i & 1 != 0 {
+                *self.buf.last_mut().unwrap() |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+
+    /// Returns the packed bytes. The last byte is padded with zeros.
+    fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+fn poss_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.position_synchronisation().unwrap();
+    let mut buf = vec![timestamp_format_to_byte(content.timestamp_format)];
+    buf.extend(&content.position.to_be_bytes());
+    buf
+}
+
+fn rvad_to_bytes(request: EncoderRequest) -> crate::Result<Vec<u8>> {
+    let content = request.content.volume_adjustment().unwrap();
+    if content.bits == 0 || content.bits > 64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "the number of bits of a volume adjustment must be between 1 and 64",
+        ));
+    }
+    let len = usize::from(content.bits).div_ceil(8);
+    let has_bass = content.bass.is_some();
+    let has_centre = has_bass || content.centre.is_some();
+    let has_back = has_centre || content.back_right.is_some() || content.back_left.is_some();
+    let back_right = content.back_right.unwrap_or_default();
+    let back_left = content.back_left.unwrap_or_default();
+    let centre = content.centre.unwrap_or_default();
+    let bass = content.bass.unwrap_or_default();
+
+    let mut flags = 0;
+    for (i, change) in [
+        content.right,
+        content.left,
+        back_right,
+        back_left,
+        centre,
+        bass,
+    ]
+    .iter()
+    .enumerate()
+    {
+        if change.adjustment > 0 {
+            flags |= 1 << i;
+        }
+    }
+    let mut buf = vec![flags, content.bits];
+    let mut push = |value: u64| buf.extend(&value.to_be_bytes()[8 - len..]);
+    push(content.right.adjustment.unsigned_abs());
+    push(content.left.adjustment.unsigned_abs());
+    push(content.right.peak);
+    push(content.left.peak);
+    if has_back {
+        push(back_right.adjustment.unsigned_abs());
+        push(back_left.adjustment.unsigned_abs());
+        push(back_right.peak);
+        push(back_left.peak);
+    }
+    if has_centre {
+        push(centre.adjustment.unsigned_abs());
+        push(centre.peak);
+    }
+    if has_bass {
+        push(bass.adjustment.unsigned_abs());
+        push(bass.peak);
+    }
+    Ok(buf)
+}
+
+/// Encodes a counter as a big-endian integer of at least 4 bytes.
+fn counter_to_bytes(counter: u64) -> Vec<u8> {
+    let bytes = counter.to_be_bytes();
+    let start = bytes
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(bytes.len())
+        .min(4);
+    bytes[start..].to_vec()
+}
+
+fn pcnt_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    counter_to_bytes(request.content.play_counter().unwrap())
+}
+
+fn popm_to_bytes(request: EncoderRequest) -> Vec<u8> {
+    let content = request.content.popularimeter().unwrap();
+    let mut buf = string_to_latin1(&content.user);
+    buf.push(0);
+    buf.push(content.rating);
+    if content.counter > 0 {
+        buf.extend(counter_to_bytes(content.counter));
+    }
+    buf
+}
+
 struct DecodingParams<'a> {
     encoding: Encoding,
     string_func: Box<Fn(&[u8]) -> crate::Result<String> + 'a>,
@@ -452,6 +1026,15 @@ macro_rules! decode_part {
         };
         (ty, &$bytes[1..])
     }};
+    ($bytes:expr, $params:ident, byte()) => {{
+        if $bytes.is_empty() {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode byte",
+            ));
+        }
+        ($bytes[0], &$bytes[1..])
+    }};
     ($bytes:expr, $params:ident, bytes()) => {{
         ($bytes.to_vec(), &$bytes[0..0])
     }};
@@ -520,6 +1103,13 @@ fn parse_apic_v3(data: &[u8]) -> crate::Result<Content> {
                    description: string(true), data: bytes());
 }
 
+/// Attempts to parse the data as a general encapsulated object frame.
+/// Returns a `Content::EncapsulatedObject`.
+fn parse_geob(data: &[u8]) -> crate::Result<Content> {
+    decode!(data, EncapsulatedObject, mime_type: latin1(true), filename: string(true),
+            description: string(true), data: bytes())
+}
+
 /// Attempts to parse the data as a comment frame.
 /// Returns a `Content::Comment`.
 fn parse_comm(data: &[u8]) -> crate::Result<Content> {
@@ -527,6 +1117,275 @@ fn parse_comm(data: &[u8]) -> crate::Result<Content> {
                    text: string(false));
 }
 
+/// Attempts to parse the data as a terms of use frame.
+/// Returns a `Content::TermsOfUse`.
+fn parse_user(data: &[u8]) -> crate::Result<Content> {
+    decode!(data, TermsOfUse, lang: fixed_string(3), text: string(false))
+}
+
+/// Decodes a date in the format YYYYMMDD that is followed by the remainder of the data.
+fn parse_date(data: &[u8]) -> crate::Result<(String, &[u8])> {
+    if data.len() < 8 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode date",
+        ));
+    }
+    Ok((string_from_latin1(&data[..8])?, &data[8..]))
+}
+
+/// Attempts to parse the data as an ownership frame.
+/// Returns a `Content::Ownership`.
+fn parse_owne(data: &[u8]) -> crate::Result<Content> {
+    assert_data!(data);
+    let encoding = encoding_from_byte(data[0])?;
+    let params = DecodingParams::for_encoding(encoding);
+    let (price_paid, next) = decode_part!(&data[1..], params, latin1(true));
+    let (date_of_purchase, next) = parse_date(next)?;
+    let (seller, _) = decode_part!(next, params, string(false));
+    Ok(Content::Ownership(Ownership {
+        price_paid,
+        date_of_purchase,
+        seller,
+    }))
+}
+
+/// Attempts to parse the data as a commercial frame.
+/// Returns a `Content::Commercial`.
+fn parse_comr(data: &[u8]) -> crate::Result<Content> {
+    assert_data!(data);
+    let encoding = encoding_from_byte(data[0])?;
+    let params = DecodingParams::for_encoding(encoding);
+    let (price, next) = decode_part!(&data[1..], params, latin1(true));
+    let (valid_until, next) = parse_date(next)?;
+    let (contact_url, next) = decode_part!(next, params, latin1(true));
+    let (received_as, next) = decode_part!(next, params, byte());
+    let (seller, next) = decode_part!(next, params, string(true));
+    // The description is the last field if there is no seller logo, some writers omit its
+    // terminator in that case.
+    let (description, next) = decode_part!(next, params, text());
+    let seller_logo = if next.is_empty() {
+        None
+    } else {
+        let (mime_type, next) = decode_part!(next, params, latin1(true));
+        Some(SellerLogo {
+            mime_type,
+            data: next.to_vec(),
+        })
+    };
+    Ok(Content::Commercial(Commercial {
+        price,
+        valid_until,
+        contact_url,
+        received_as: received_as.into(),
+        seller,
+        description,
+        seller_logo,
+    }))
+}
+
+/// Attempts to parse the data as a linked information frame.
+/// Returns a `Content::LinkedInformation`.
+///
+/// ID3v2.2 frame identifiers are converted to their ID3v2.3/ID3v2.4 equivalent.
+fn parse_link(data: &[u8], version: tag::Version) -> crate::Result<Content> {
+    let id_len = if version == tag::Id3v22 { 3 } else { 4 };
+    if data.len() < id_len {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode linked frame identifier",
+        ));
+    }
+    let id = string_from_latin1(&data[..id_len])?;
+    let frame_identifier = match convert_id_2_to_3(&id) {
+        Some(id) if version == tag::Id3v22 => id.to_string(),
+        _ => id,
+    };
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (url, next) = decode_part!(&data[id_len..], params, string(true));
+    let next = next.strip_suffix(&[0]).unwrap_or(next);
+    let additional_data = if next.is_empty() {
+        Vec::new()
+    } else {
+        next.split(|&b| b == 0)
+            .map(string_from_latin1)
+            .collect::<crate::Result<_>>()?
+    };
+    Ok(Content::LinkedInformation(LinkedInformation {
+        frame_identifier,
+        url,
+        additional_data,
+    }))
+}
+
+/// Attempts to parse the data as an audio seek point index frame.
+/// Returns a `Content::AudioSeekPointIndex`.
+fn parse_aspi(data: &[u8]) -> crate::Result<Content> {
+    if data.len() < 11 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode audio seek point index",
+        ));
+    }
+    let num_points = usize::from(BigEndian::read_u16(&data[8..10]));
+    let bits_per_index_point = data[10];
+    let points = &data[11..];
+    let index_points = match bits_per_index_point {
+        8 if points.len() >= num_points => points[..num_points]
+            .iter()
+            .map(|&point| u16::from(point))
+            .collect(),
+        16 if points.len() >= num_points * 2 => points[..num_points * 2]
+            .chunks_exact(2)
+            .map(BigEndian::read_u16)
+            .collect(),
+        8 | 16 => {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode audio seek point index",
+            ))
+        }
+        _ => {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "unsupported number of bits per index point",
+            ))
+        }
+    };
+    Ok(Content::AudioSeekPointIndex(AudioSeekPointIndex {
+        indexed_data_start: BigEndian::read_u32(&data[0..4]),
+        indexed_data_length: BigEndian::read_u32(&data[4..8]),
+        bits_per_index_point,
+        index_points,
+    }))
+}
+
+/// Attempts to parse the data as a seek frame.
+/// Returns a `Content::SeekOffset`.
+fn parse_seek(data: &[u8]) -> crate::Result<Content> {
+    if data.len() < 4 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode seek offset",
+        ));
+    }
+    Ok(Content::SeekOffset(BigEndian::read_u32(data)))
+}
+
+/// Attempts to parse the data as an ID3v2.4 equalisation frame.
+/// Returns a `Content::Equalisation`.
+fn parse_equ2(data: &[u8]) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (interpolation_method, next) = decode_part!(data, params, byte());
+    let (identification, next) = decode_part!(next, params, string(true));
+    let points = next.chunks_exact(4);
+    if !points.remainder().is_empty() {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode equalisation adjustment",
+        ));
+    }
+    Ok(Content::Equalisation(Equalisation {
+        interpolation_method: interpolation_method.into(),
+        identification,
+        adjustments: points
+            .map(|point| (BigEndian::read_u16(point), BigEndian::read_i16(&point[2..])))
+            .collect(),
+    }))
+}
+
+/// Attempts to parse the data as an ID3v2.2/ID3v2.3 equalisation frame.
+/// Returns a `Content::LegacyEqualisation`.
+///
+/// Frames with adjustments of more than 64 bits or adjustments that do not fit in 63 bits are
+/// returned as `Content::Unknown`.
+fn parse_equa(data: Vec<u8>) -> crate::Result<Content> {
+    let bits = match data.first() {
+        Some(0) => {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "unsupported number of bits for equalisation adjustments",
+            ))
+        }
+        Some(&bits) if bits > 64 => return Ok(Content::Unknown(data)),
+        Some(&bits) => bits,
+        None => {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode equalisation",
+            ))
+        }
+    };
+    let len = usize::from(bits).div_ceil(8);
+    let points = data[1..]
+        .chunks_exact(2 + len)
+        .map(|point| {
+            let frequency = BigEndian::read_u16(point);
+            let adjustment = point[2..].iter().fold(0u64, |n, &b| n << 8 | u64::from(b));
+            (frequency, adjustment)
+        })
+        .collect::<Vec<_>>();
+    if points
+        .iter()
+        .any(|&(_, adjustment)| adjustment > i64::MAX as u64)
+    {
+        return Ok(Content::Unknown(data));
+    }
+    let adjustments = points
+        .into_iter()
+        .map(|(frequency, adjustment)| {
+            if frequency & 0x8000 != 0 {
+                (frequency & 0x7FFF, adjustment as i64)
+            } else {
+                (frequency, -(adjustment as i64))
+            }
+        })
+        .collect();
+    Ok(Content::LegacyEqualisation(LegacyEqualisation {
+        bits,
+        adjustments,
+    }))
+}
+
+/// Attempts to parse the data as a reverb frame.
+/// Returns a `Content::Reverb`.
+fn parse_rvrb(data: &[u8]) -> crate::Result<Content> {
+    if data.len() < 12 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode reverb",
+        ));
+    }
+    Ok(Content::Reverb(Reverb {
+        reverb_left: BigEndian::read_u16(&data[0..2]),
+        reverb_right: BigEndian::read_u16(&data[2..4]),
+        bounces_left: data[4],
+        bounces_right: data[5],
+        feedback_left_to_left: data[6],
+        feedback_left_to_right: data[7],
+        feedback_right_to_right: data[8],
+        feedback_right_to_left: data[9],
+        premix_left_to_right: data[10],
+        premix_right_to_left: data[11],
+    }))
+}
+
+/// Attempts to parse the data as a recommended buffer size frame.
+/// Returns a `Content::RecommendedBufferSize`.
+fn parse_rbuf(data: &[u8]) -> crate::Result<Content> {
+    if data.len() < 4 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode recommended buffer size",
+        ));
+    }
+    Ok(Content::RecommendedBufferSize(RecommendedBufferSize {
+        buffer_size: BigEndian::read_u24(&data[0..3]),
+        embedded_info: data[3] & 1 != 0,
+        offset_to_next_tag: data.get(4..8).map(BigEndian::read_u32),
+    }))
+}
+
 /// Attempts to parse the data as a text frame.
 /// Returns a `Content::Text`.
 fn parse_text(data: &[u8]) -> crate::Result<Content> {
@@ -639,24 +1498,479 @@ fn parse_sylt(data: &[u8]) -> crate::Result<Content> {
     }))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::frame::Content;
-    use crate::frame::{self, Picture, PictureType};
-    use std::collections::HashMap;
-
-    fn bytes_for_encoding(text: &str, encoding: Encoding) -> Vec<u8> {
-        match encoding {
-            //string.chars().map(|c| c as u8)
-            Encoding::Latin1 => text.chars().map(|c| c as u8).collect(),
-            Encoding::UTF8 => text.as_bytes().to_vec(),
-            Encoding::UTF16 => string_to_utf16(text),
-            Encoding::UTF16BE => string_to_utf16be(text),
-        }
-    }
+/// Attempts to parse the data as a group identification registration frame.
+/// Returns a `Content::GroupIdentificationRegistration`.
+fn parse_grid(data: &[u8]) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (owner_identifier, next) = decode_part!(data, params, string(true));
+    let (group_symbol, next) = decode_part!(next, params, byte());
+    let (data, _) = decode_part!(next, params, bytes());
+    Ok(Content::GroupIdentificationRegistration(
+        GroupIdentificationRegistration {
+            owner_identifier,
+            group_symbol,
+            data,
+        },
+    ))
+}
 
-    fn delim_for_encoding(encoding: Encoding) -> Vec<u8> {
+/// Attempts to parse the data as an encryption method registration frame.
+/// Returns a `Content::EncryptionMethodRegistration`.
+fn parse_encr(data: &[u8]) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (owner_identifier, next) = decode_part!(data, params, string(true));
+    let (method_symbol, next) = decode_part!(next, params, byte());
+    let (data, _) = decode_part!(next, params, bytes());
+    Ok(Content::EncryptionMethodRegistration(
+        EncryptionMethodRegistration {
+            owner_identifier,
+            method_symbol,
+            data,
+        },
+    ))
+}
+
+/// Attempts to parse the data as a unique file identifier frame.
+/// Returns a `Content::UniqueFileIdentifier`.
+fn parse_ufid(data: &[u8]) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (owner_identifier, next) = decode_part!(data, params, string(true));
+    let (identifier, _) = decode_part!(next, params, bytes());
+    Ok(Content::UniqueFileIdentifier(UniqueFileIdentifier {
+        owner_identifier,
+        identifier,
+    }))
+}
+
+/// Attempts to parse the data as a private frame.
+/// Returns a `Content::Private`.
+fn parse_priv(data: &[u8]) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (owner_identifier, next) = decode_part!(data, params, string(true));
+    let (data, _) = decode_part!(next, params, bytes());
+    Ok(Content::Private(Private {
+        owner_identifier,
+        data,
+    }))
+}
+
+/// Decodes the frames that are embedded in a chapter or table of contents frame.
+fn parse_embedded_frames(mut data: &[u8], version: tag::Version) -> crate::Result<Vec<Frame>> {
+    let mut frames = Vec::new();
+    while !data.is_empty() {
+        let decoded = match version {
+            tag::Id3v22 => super::v2::decode(data)?,
+            tag::Id3v23 | tag::Id3v24 => super::decode(data, version, false, &Ciphers::new())?,
+        };
+        let (size, frame) = match decoded {
+            Some(decoded) => decoded,
+            // The remaining data is padding.
+            None => break,
+        };
+        if size > data.len() {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "an embedded frame exceeds the size of its parent frame",
+            ));
+        }
+        frames.push(frame);
+        data = &data[size..];
+    }
+    Ok(frames)
+}
+
+/// Attempts to parse the data as a chapter frame.
+/// Returns a `Content::Chapter`.
+fn parse_chap(data: &[u8], version: tag::Version) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (element_id, next) = decode_part!(data, params, string(true));
+    if next.len() < 16 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode chapter times",
+        ));
+    }
+    let offset = |i| match BigEndian::read_u32(&next[i..i + 4]) {
+        0xFFFF_FFFF => None,
+        offset => Some(offset),
+    };
+    Ok(Content::Chapter(Chapter {
+        element_id,
+        start_time: BigEndian::read_u32(&next[0..4]),
+        end_time: BigEndian::read_u32(&next[4..8]),
+        start_offset: offset(8),
+        end_offset: offset(12),
+        frames: parse_embedded_frames(&next[16..], version)?,
+    }))
+}
+
+/// Attempts to parse the data as a table of contents frame.
+/// Returns a `Content::TableOfContents`.
+fn parse_ctoc(data: &[u8], version: tag::Version) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (element_id, next) = decode_part!(data, params, string(true));
+    let (flags, next) = decode_part!(next, params, byte());
+    let (entry_count, mut next) = decode_part!(next, params, byte());
+    let mut elements = Vec::with_capacity(usize::from(entry_count));
+    for _ in 0..entry_count {
+        let (child_id, child_next) = decode_part!(next, params, string(true));
+        elements.push(child_id);
+        next = child_next;
+    }
+    Ok(Content::TableOfContents(TableOfContents {
+        element_id,
+        top_level: flags & 0x02 != 0,
+        ordered: flags & 0x01 != 0,
+        elements,
+        frames: parse_embedded_frames(next, version)?,
+    }))
+}
+
+/// Attempts to parse the data as a music CD identifier frame.
+/// Returns a `Content::MusicCdIdentifier`.
+///
+/// Some applications store the table of contents in a different format, in which case the data
+/// is returned as `Content::Unknown`.
+fn parse_mcdi(data: Vec<u8>) -> crate::Result<Content> {
+    if data.len() < 4 || !data[4..].chunks_exact(8).remainder().is_empty() {
+        return Ok(Content::Unknown(data));
+    }
+    let tracks = data[4..]
+        .chunks_exact(8)
+        .map(|descriptor| CdTrack {
+            number: descriptor[2],
+            adr_control: descriptor[1],
+            address: BigEndian::read_u32(&descriptor[4..8]),
+        })
+        .collect();
+    Ok(Content::MusicCdIdentifier(MusicCdIdentifier {
+        first_track: data[2],
+        last_track: data[3],
+        tracks,
+    }))
+}
+
+/// Attempts to parse the data as an ID3v2.4 relative volume adjustment frame.
+/// Returns a `Content::RelativeVolumeAdjustment`.
+fn parse_rva2(data: &[u8]) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (identification, mut next) = decode_part!(data, params, string(true));
+    let mut channels = Vec::new();
+    while !next.is_empty() {
+        if next.len() < 4 {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode channel adjustment",
+            ));
+        }
+        let peak_bits = next[3];
+        let peak_len = usize::from(peak_bits).div_ceil(8);
+        if next.len() < 4 + peak_len {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode peak volume",
+            ));
+        }
+        channels.push(ChannelAdjustment {
+            channel_type: next[0].into(),
+            volume_adjustment: BigEndian::read_i16(&next[1..3]),
+            peak_bits,
+            peak: next[4..4 + peak_len].to_vec(),
+        });
+        next = &next[4 + peak_len..];
+    }
+    Ok(Content::RelativeVolumeAdjustment(
+        RelativeVolumeAdjustment {
+            identification,
+            channels,
+        },
+    ))
+}
+
+/// Attempts to parse the data as an ID3v2.2/ID3v2.3 relative volume adjustment frame.
+/// Returns a `Content::VolumeAdjustment`.
+///
+/// Frames with values of more than 64 bits or adjustments that do not fit in 63 bits are returned
+/// as `Content::Unknown`.
+fn parse_rvad(data: Vec<u8>) -> crate::Result<Content> {
+    if data.len() < 2 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode volume adjustment",
+        ));
+    }
+    let (flags, bits) = (data[0], data[1]);
+    if bits == 0 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "unsupported number of bits for volume adjustment",
+        ));
+    }
+    if bits > 64 {
+        return Ok(Content::Unknown(data));
+    }
+    let len = usize::from(bits).div_ceil(8);
+    let values = data[2..]
+        .chunks_exact(len)
+        .map(|value| value.iter().fold(0u64, |n, &b| n << 8 | u64::from(b)))
+        .collect::<Vec<_>>();
+    if values.len() < 4 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode volume adjustment",
+        ));
+    }
+    // The positions of the adjustments of each channel, the other values are peaks.
+    let adjustment_indices = [0, 1, 4, 5, 8, 10];
+    if adjustment_indices
+        .iter()
+        .any(|&i| values.get(i).is_some_and(|&v| v > i64::MAX as u64))
+    {
+        return Ok(Content::Unknown(data));
+    }
+    let change = |i: usize, adjustment: u64, peak: u64| VolumeChange {
+        adjustment: if flags & 1 << i != 0 {
+            adjustment as i64
+        } else {
+            -(adjustment as i64)
+        },
+        peak,
+    };
+    let (back_right, back_left) = if values.len() >= 8 {
+        (
+            Some(change(2, values[4], values[6])),
+            Some(change(3, values[5], values[7])),
+        )
+    } else {
+        (None, None)
+    };
+    Ok(Content::VolumeAdjustment(VolumeAdjustment {
+        bits,
+        right: change(0, values[0], values[2]),
+        left: change(1, values[1], values[3]),
+        back_right,
+        back_left,
+        centre: values.get(8..10).map(|v| change(4, v[0], v[1])),
+        bass: values.get(10..12).map(|v| change(5, v[0], v[1])),
+    }))
+}
+
+/// Attempts to parse the timestamp format byte of an ETCO, SYTC or POSS frame.
+fn parse_timestamp_format(data: &[u8]) -> crate::Result<TimestampFormat> {
+    match data.first() {
+        Some(1) => Ok(TimestampFormat::MPEG),
+        Some(2) => Ok(TimestampFormat::MS),
+        Some(_) => Err(Error::new(ErrorKind::Parsing, "invalid timestamp format")),
+        None => Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode timestamp format",
+        )),
+    }
+}
+
+/// Decodes a big-endian timestamp that is followed by the remainder of the data.
+fn parse_timestamp(data: &[u8]) -> crate::Result<(u32, &[u8])> {
+    if data.len() < 4 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode timestamp",
+        ));
+    }
+    Ok((BigEndian::read_u32(data), &data[4..]))
+}
+
+/// Attempts to parse the data as an event timing codes frame.
+/// Returns a `Content::EventTimingCodes`.
+fn parse_etco(data: &[u8]) -> crate::Result<Content> {
+    let timestamp_format = parse_timestamp_format(data)?;
+    let mut next = &data[1..];
+    let mut events = Vec::new();
+    while let Some((&event_type, rest)) = next.split_first() {
+        let (timestamp, rest) = parse_timestamp(rest)?;
+        events.push((timestamp, EventType::from(event_type)));
+        next = rest;
+    }
+    Ok(Content::EventTimingCodes(EventTimingCodes {
+        timestamp_format,
+        events,
+    }))
+}
+
+/// Attempts to parse the data as a synchronised tempo codes frame.
+/// Returns a `Content::SynchronisedTempoCodes`.
+fn parse_sytc(data: &[u8]) -> crate::Result<Content> {
+    let timestamp_format = parse_timestamp_format(data)?;
+    let mut next = &data[1..];
+    let mut tempo = Vec::new();
+    while let Some((&first, rest)) = next.split_first() {
+        let (bpm, rest) = match (first, rest.split_first()) {
+            (0xFF, Some((&second, rest))) => (0xFF + u16::from(second), rest),
+            (0xFF, None) => {
+                return Err(Error::new(
+                    ErrorKind::Parsing,
+                    "insufficient data to decode tempo",
+                ))
+            }
+            _ => (u16::from(first), rest),
+        };
+        let (timestamp, rest) = parse_timestamp(rest)?;
+        tempo.push((timestamp, bpm));
+        next = rest;
+    }
+    Ok(Content::SynchronisedTempoCodes(SynchronisedTempoCodes {
+        timestamp_format,
+        tempo,
+    }))
+}
+
+/// Reads a value of the specified number of bits, starting at a bit offset in the data. The most
+/// significant bit comes first.
+fn read_bits(data: &[u8], offset: usize, bits: u8) -> u32 {
+    (offset..offset + usize::from(bits)).fold(0u32, |value, i| {
+        let bit = data[i / 8] >> (7 - i % 8) & 1;
+        value.wrapping_shl(1) | u32::from(bit)
+    })
+}
+
+/// Attempts to parse the data as an MPEG location lookup table frame.
+/// Returns a `Content::MpegLocationLookupTable`.
+fn parse_mllt(data: &[u8]) -> crate::Result<Content> {
+    if data.len() < 10 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode MPEG location lookup table",
+        ));
+    }
+    let (bits_for_bytes, bits_for_millis) = (data[8], data[9]);
+    if bits_for_bytes > 32 || bits_for_millis > 32 || bits_for_bytes + bits_for_millis == 0 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "unsupported number of bits for MPEG location lookup table deviations",
+        ));
+    }
+    let table = &data[10..];
+    let reference_bits = usize::from(bits_for_bytes) + usize::from(bits_for_millis);
+    let table_bits = table.len() * 8;
+    let mut num_references = table_bits / reference_bits;
+    // The last byte may be padded with zeros. Small references may fit in the padding, so a
+    // trailing reference that consists of zeros only and lies within the last seven bits is
+    // considered to be padding.
+    while num_references > 0 {
+        let offset = (num_references - 1) * reference_bits;
+        let is_padding = table_bits - offset < 8
+            && read_bits(table, offset, bits_for_bytes) == 0
+            && read_bits(table, offset + usize::from(bits_for_bytes), bits_for_millis) == 0;
+        if !is_padding {
+            break;
+        }
+        num_references -= 1;
+    }
+    let references = (0..num_references)
+        .map(|i| {
+            let offset = i * reference_bits;
+            MpegLocationLookupTableReference {
+                deviate_bytes: read_bits(table, offset, bits_for_bytes),
+                deviate_millis: read_bits(
+                    table,
+                    offset + usize::from(bits_for_bytes),
+                    bits_for_millis,
+                ),
+            }
+        })
+        .collect();
+    Ok(Content::MpegLocationLookupTable(MpegLocationLookupTable {
+        frames_between_reference: BigEndian::read_u16(&data[0..2]),
+        bytes_between_reference: BigEndian::read_u24(&data[2..5]),
+        millis_between_reference: BigEndian::read_u24(&data[5..8]),
+        bits_for_bytes,
+        bits_for_millis,
+        references,
+    }))
+}
+
+/// Attempts to parse the data as a position synchronisation frame.
+/// Returns a `Content::PositionSynchronisation`.
+fn parse_poss(data: &[u8]) -> crate::Result<Content> {
+    let timestamp_format = parse_timestamp_format(data)?;
+    let position = &data[1..];
+    if position.is_empty() || position.len() > 4 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "invalid position synchronisation position",
+        ));
+    }
+    Ok(Content::PositionSynchronisation(PositionSynchronisation {
+        timestamp_format,
+        position: position.iter().fold(0, |n, &b| n << 8 | u32::from(b)),
+    }))
+}
+
+/// Decodes a big-endian counter of arbitrary length. Returns None if the counter does not fit in
+/// 64 bits.
+fn counter_from_bytes(data: &[u8]) -> Option<u64> {
+    data.iter().try_fold(0u64, |counter, &b| {
+        counter.checked_mul(256).map(|c| c + u64::from(b))
+    })
+}
+
+/// Attempts to parse the data as a play counter frame.
+/// Returns a `Content::PlayCounter`.
+///
+/// Counters that do not fit in 64 bits are returned as `Content::Unknown`, so they are retained
+/// when the frame is written.
+fn parse_pcnt(data: Vec<u8>) -> crate::Result<Content> {
+    if data.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode play counter",
+        ));
+    }
+    Ok(match counter_from_bytes(&data) {
+        Some(counter) => Content::PlayCounter(counter),
+        None => Content::Unknown(data),
+    })
+}
+
+/// Attempts to parse the data as a popularimeter frame.
+/// Returns a `Content::Popularimeter`.
+///
+/// Counters that do not fit in 64 bits are returned as `Content::Unknown`, so they are retained
+/// when the frame is written.
+fn parse_popm(data: Vec<u8>) -> crate::Result<Content> {
+    let params = DecodingParams::for_encoding(Encoding::Latin1);
+    let (user, next) = decode_part!(&data[..], params, string(true));
+    let (rating, next) = decode_part!(next, params, byte());
+    Ok(match counter_from_bytes(next) {
+        Some(counter) => Content::Popularimeter(Popularimeter {
+            user,
+            rating,
+            counter,
+        }),
+        None => Content::Unknown(data),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::Content;
+    use crate::frame::{
+        self, ChannelType, EncapsulatedObject, InterpolationMethod, Picture, PictureType,
+        ReceivedAs, TermsOfUse,
+    };
+    use std::collections::HashMap;
+
+    fn bytes_for_encoding(text: &str, encoding: Encoding) -> Vec<u8> {
+        match encoding {
+            //string.chars().map(|c| c as u8)
+            Encoding::Latin1 => text.chars().map(|c| c as u8).collect(),
+            Encoding::UTF8 => text.as_bytes().to_vec(),
+            Encoding::UTF16 => string_to_utf16(text),
+            Encoding::UTF16BE => string_to_utf16be(text),
+        }
+    }
+
+    fn delim_for_encoding(encoding: Encoding) -> Vec<u8> {
         match encoding {
             Encoding::Latin1 | Encoding::UTF8 => vec![0],
             Encoding::UTF16 | Encoding::UTF16BE => vec![0, 0],
@@ -665,7 +1979,7 @@ mod tests {
 
     #[test]
     fn test_apic_v2() {
-        assert!(decode("PIC", &[][..]).is_err());
+        assert!(decode("PIC", tag::Id3v22, &[][..]).is_err());
 
         let mut format_map = HashMap::new();
         format_map.insert("image/jpeg", "JPG");
@@ -693,7 +2007,10 @@ mod tests {
                     data.extend(picture_data.iter().cloned());
 
                     assert_eq!(
-                        *decode("PIC", &data[..]).unwrap().picture().unwrap(),
+                        *decode("PIC", tag::Id3v22, &data[..])
+                            .unwrap()
+                            .picture()
+                            .unwrap(),
                         picture
                     );
                     let mut data_out = Vec::new();
@@ -712,7 +2029,7 @@ mod tests {
 
     #[test]
     fn test_apic_v3() {
-        assert!(decode("APIC", &[][..]).is_err());
+        assert!(decode("APIC", tag::Id3v24, &[][..]).is_err());
 
         for mime_type in &["", "image/jpeg"] {
             for description in &["", "description"] {
@@ -742,7 +2059,10 @@ mod tests {
                     data.extend(picture_data.iter().cloned());
 
                     assert_eq!(
-                        *decode("APIC", &data[..]).unwrap().picture().unwrap(),
+                        *decode("APIC", tag::Id3v24, &data[..])
+                            .unwrap()
+                            .picture()
+                            .unwrap(),
                         picture
                     );
                     let mut data_out = Vec::new();
@@ -761,7 +2081,7 @@ mod tests {
 
     #[test]
     fn test_comm() {
-        assert!(decode("COMM", &[][..]).is_err());
+        assert!(decode("COMM", tag::Id3v24, &[][..]).is_err());
 
         println!("valid");
         for description in &["", "description"] {
@@ -786,7 +2106,10 @@ mod tests {
                         text: comment.to_string(),
                     };
                     assert_eq!(
-                        *decode("COMM", &data[..]).unwrap().comment().unwrap(),
+                        *decode("COMM", tag::Id3v24, &data[..])
+                            .unwrap()
+                            .comment()
+                            .unwrap(),
                         content
                     );
                     let mut data_out = Vec::new();
@@ -817,7 +2140,7 @@ mod tests {
             data.extend(b"eng".iter().cloned());
             data.extend(bytes_for_encoding(description, *encoding).into_iter());
             data.extend(bytes_for_encoding(comment, *encoding).into_iter());
-            assert!(decode("COMM", &data[..]).is_err());
+            assert!(decode("COMM", tag::Id3v24, &data[..]).is_err());
         }
         println!("Empty description");
         let comment = "comment";
@@ -841,7 +2164,10 @@ mod tests {
             println!("data == {:?}", data);
             println!("content == {:?}", content);
             assert_eq!(
-                *decode("COMM", &data[..]).unwrap().comment().unwrap(),
+                *decode("COMM", tag::Id3v24, &data[..])
+                    .unwrap()
+                    .comment()
+                    .unwrap(),
                 content
             );
         }
@@ -849,7 +2175,7 @@ mod tests {
 
     #[test]
     fn test_text() {
-        assert!(decode("TALB", &[][..]).is_err());
+        assert!(decode("TALB", tag::Id3v24, &[][..]).is_err());
 
         for text in &["", "text"] {
             for encoding in &[
@@ -863,7 +2189,13 @@ mod tests {
                 data.push(*encoding as u8);
                 data.extend(bytes_for_encoding(text, *encoding).into_iter());
 
-                assert_eq!(decode("TALB", &data[..]).unwrap().text().unwrap(), *text);
+                assert_eq!(
+                    decode("TALB", tag::Id3v24, &data[..])
+                        .unwrap()
+                        .text()
+                        .unwrap(),
+                    *text
+                );
                 let mut data_out = Vec::new();
                 encode(
                     &mut data_out,
@@ -879,7 +2211,7 @@ mod tests {
 
     #[test]
     fn test_null_terminated_text() {
-        assert!(decode("TRCK", &[][..]).is_err());
+        assert!(decode("TRCK", tag::Id3v24, &[][..]).is_err());
         let text = "text\u{0}\u{0}";
         for encoding in &[
             Encoding::Latin1,
@@ -892,7 +2224,13 @@ mod tests {
             data.push(*encoding as u8);
             data.extend(bytes_for_encoding(text, *encoding).into_iter());
 
-            assert_eq!(decode("TALB", &data[..]).unwrap().text().unwrap(), "text");
+            assert_eq!(
+                decode("TALB", tag::Id3v24, &data[..])
+                    .unwrap()
+                    .text()
+                    .unwrap(),
+                "text"
+            );
             let mut data_out = Vec::new();
             encode(
                 &mut data_out,
@@ -907,7 +2245,7 @@ mod tests {
 
     #[test]
     fn test_txxx() {
-        assert!(decode("TXXX", &[][..]).is_err());
+        assert!(decode("TXXX", tag::Id3v24, &[][..]).is_err());
 
         println!("valid");
         for key in &["", "key"] {
@@ -930,7 +2268,10 @@ mod tests {
                         value: value.to_string(),
                     };
                     assert_eq!(
-                        *decode("TXXX", &data[..]).unwrap().extended_text().unwrap(),
+                        *decode("TXXX", tag::Id3v24, &data[..])
+                            .unwrap()
+                            .extended_text()
+                            .unwrap(),
                         content
                     );
                     let mut data_out = Vec::new();
@@ -960,7 +2301,7 @@ mod tests {
             data.push(*encoding as u8);
             data.extend(bytes_for_encoding(key, *encoding).into_iter());
             data.extend(bytes_for_encoding(value, *encoding).into_iter());
-            assert!(decode("TXXX", &data[..]).is_err());
+            assert!(decode("TXXX", tag::Id3v24, &data[..]).is_err());
         }
     }
 
@@ -970,7 +2311,13 @@ mod tests {
             println!("`{:?}`", link);
             let data = link.as_bytes().to_vec();
 
-            assert_eq!(decode("WOAF", &data[..]).unwrap().link().unwrap(), *link);
+            assert_eq!(
+                decode("WOAF", tag::Id3v24, &data[..])
+                    .unwrap()
+                    .link()
+                    .unwrap(),
+                *link
+            );
             let mut data_out = Vec::new();
             encode(
                 &mut data_out,
@@ -985,7 +2332,7 @@ mod tests {
 
     #[test]
     fn test_wxxx() {
-        assert!(decode("WXXX", &[][..]).is_err());
+        assert!(decode("WXXX", tag::Id3v24, &[][..]).is_err());
 
         println!("valid");
         for description in &["", "rust"] {
@@ -1008,7 +2355,10 @@ mod tests {
                         link: link.to_string(),
                     };
                     assert_eq!(
-                        *decode("WXXX", &data[..]).unwrap().extended_link().unwrap(),
+                        *decode("WXXX", tag::Id3v24, &data[..])
+                            .unwrap()
+                            .extended_link()
+                            .unwrap(),
                         content
                     );
                     let mut data_out = Vec::new();
@@ -1038,13 +2388,13 @@ mod tests {
             data.push(*encoding as u8);
             data.extend(bytes_for_encoding(description, *encoding).into_iter());
             data.extend(bytes_for_encoding(link, Encoding::Latin1).into_iter());
-            assert!(decode("WXXX", &data[..]).is_err());
+            assert!(decode("WXXX", tag::Id3v24, &data[..]).is_err());
         }
     }
 
     #[test]
     fn test_uslt() {
-        assert!(decode("USLT", &[][..]).is_err());
+        assert!(decode("USLT", tag::Id3v24, &[][..]).is_err());
 
         println!("valid");
         for description in &["", "description"] {
@@ -1069,7 +2419,10 @@ mod tests {
                         text: text.to_string(),
                     };
                     assert_eq!(
-                        *decode("USLT", &data[..]).unwrap().lyrics().unwrap(),
+                        *decode("USLT", tag::Id3v24, &data[..])
+                            .unwrap()
+                            .lyrics()
+                            .unwrap(),
                         content
                     );
                     let mut data_out = Vec::new();
@@ -1100,7 +2453,1096 @@ mod tests {
             data.extend(b"eng".iter().cloned());
             data.extend(bytes_for_encoding(description, *encoding).into_iter());
             data.extend(bytes_for_encoding(lyrics, *encoding).into_iter());
-            assert!(decode("USLT", &data[..]).is_err());
+            assert!(decode("USLT", tag::Id3v24, &data[..]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_grid() {
+        assert!(decode("GRID", tag::Id3v24, &[][..]).is_err());
+        assert!(decode("GRID", tag::Id3v24, &b"owner\0"[..]).is_err());
+
+        let grid = GroupIdentificationRegistration {
+            owner_identifier: "http://example.com".to_string(),
+            group_symbol: 0x80,
+            data: vec![1, 2, 3],
+        };
+        let mut data = Vec::new();
+        data.extend(b"http://example.com\0\x80");
+        data.extend(&[1, 2, 3]);
+        let content = decode("GRID", tag::Id3v24, &data[..]).unwrap();
+        let decoded = content.group_identification_registration().unwrap();
+        assert_eq!(grid.owner_identifier, decoded.owner_identifier);
+        assert_eq!(grid.group_symbol, decoded.group_symbol);
+        assert_eq!(grid.data, decoded.data);
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::GroupIdentificationRegistration(grid),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_encr() {
+        assert!(decode("ENCR", tag::Id3v24, &[][..]).is_err());
+
+        let encr = EncryptionMethodRegistration {
+            owner_identifier: "mailto:drm@example.com".to_string(),
+            method_symbol: 0x81,
+            data: vec![4, 5],
+        };
+        let mut data = Vec::new();
+        data.extend(b"mailto:drm@example.com\0\x81");
+        data.extend(&[4, 5]);
+        let content = decode("ENCR", tag::Id3v24, &data[..]).unwrap();
+        let decoded = content.encryption_method_registration().unwrap();
+        assert_eq!(encr.owner_identifier, decoded.owner_identifier);
+        assert_eq!(encr.method_symbol, decoded.method_symbol);
+        assert_eq!(encr.data, decoded.data);
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::EncryptionMethodRegistration(encr),
+            tag::Id3v23,
+            Encoding::UTF16,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_ufid() {
+        assert!(decode("UFID", tag::Id3v24, &[][..]).is_err());
+
+        let ufid = UniqueFileIdentifier {
+            owner_identifier: "http://musicbrainz.org".to_string(),
+            identifier: b"abcdef".to_vec(),
+        };
+        let data = b"http://musicbrainz.org\0abcdef".to_vec();
+        for id in &["UFID", "UFI"] {
+            let content = decode(id, tag::Id3v24, &data[..]).unwrap();
+            let decoded = content.unique_file_identifier().unwrap();
+            assert_eq!(ufid.owner_identifier, decoded.owner_identifier);
+            assert_eq!(ufid.identifier, decoded.identifier);
+        }
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::UniqueFileIdentifier(ufid),
+            tag::Id3v22,
+            Encoding::UTF16,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_pcnt() {
+        assert!(decode("PCNT", tag::Id3v24, &[][..]).is_err());
+        assert_eq!(
+            Some(0x0102),
+            decode("CNT", tag::Id3v22, &[0, 0, 1, 2][..])
+                .unwrap()
+                .play_counter()
+        );
+        assert_eq!(
+            Some(0x01_0000_0000),
+            decode("PCNT", tag::Id3v24, &[1, 0, 0, 0, 0][..])
+                .unwrap()
+                .play_counter()
+        );
+        assert_eq!(
+            Some(u64::MAX),
+            decode(
+                "PCNT",
+                tag::Id3v24,
+                &[0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..]
+            )
+            .unwrap()
+            .play_counter()
+        );
+        // Counters that do not fit in 64 bits are kept as is.
+        let data = [1, 0, 0, 0, 0, 0, 0, 0, 0];
+        let content = decode("PCNT", tag::Id3v24, &data[..]).unwrap();
+        assert_eq!(Some(&data[..]), content.unknown());
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(&data[..], &data_out[..]);
+
+        for &(counter, ref data) in &[
+            (0, vec![0, 0, 0, 0]),
+            (0x0102, vec![0, 0, 1, 2]),
+            (0x01_0000_0000, vec![1, 0, 0, 0, 0]),
+        ] {
+            let mut data_out = Vec::new();
+            encode(
+                &mut data_out,
+                &Content::PlayCounter(counter),
+                tag::Id3v24,
+                Encoding::UTF8,
+            )
+            .unwrap();
+            assert_eq!(*data, data_out);
+        }
+    }
+
+    #[test]
+    fn test_popm() {
+        assert!(decode("POPM", tag::Id3v24, &[][..]).is_err());
+        assert!(decode("POPM", tag::Id3v24, &b"user@example.com\0"[..]).is_err());
+
+        let content = decode("POP", tag::Id3v22, &b"user@example.com\0\xc4"[..]).unwrap();
+        let decoded = content.popularimeter().unwrap();
+        assert_eq!("user@example.com", decoded.user);
+        assert_eq!(196, decoded.rating);
+        assert_eq!(0, decoded.counter);
+
+        let mut data = b"user@example.com\0\xff".to_vec();
+        data.extend(&[1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let content = decode("POPM", tag::Id3v24, &data[..]).unwrap();
+        assert_eq!(Some(&data[..]), content.unknown());
+
+        let popm = Popularimeter {
+            user: "user@example.com".to_string(),
+            rating: 255,
+            counter: 42,
+        };
+        let mut data = b"user@example.com\0\xff".to_vec();
+        data.extend(&[0, 0, 0, 42]);
+        let content = decode("POPM", tag::Id3v24, &data[..]).unwrap();
+        let decoded = content.popularimeter().unwrap();
+        assert_eq!(popm.user, decoded.user);
+        assert_eq!(popm.rating, decoded.rating);
+        assert_eq!(popm.counter, decoded.counter);
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::Popularimeter(popm),
+            tag::Id3v23,
+            Encoding::UTF16,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_geob() {
+        assert!(decode("GEOB", tag::Id3v24, &[][..]).is_err());
+
+        for filename in &["", "cues.bin"] {
+            let object = EncapsulatedObject {
+                mime_type: "application/octet-stream".to_string(),
+                filename: filename.to_string(),
+                description: "Serato Markers2".to_string(),
+                data: vec![0x01, 0x01, 0x00, 0xFF],
+            };
+
+            for encoding in &[
+                Encoding::Latin1,
+                Encoding::UTF8,
+                Encoding::UTF16,
+                Encoding::UTF16BE,
+            ] {
+                let mut data = Vec::new();
+                data.push(*encoding as u8);
+                data.extend(object.mime_type.bytes());
+                data.push(0x0);
+                data.extend(bytes_for_encoding(filename, *encoding));
+                data.extend(delim_for_encoding(*encoding));
+                data.extend(bytes_for_encoding(&object.description, *encoding));
+                data.extend(delim_for_encoding(*encoding));
+                data.extend(object.data.iter().cloned());
+
+                for id in &["GEOB", "GEO"] {
+                    let content = decode(id, tag::Id3v24, &data[..]).unwrap();
+                    let decoded = content.encapsulated_object().unwrap();
+                    assert_eq!(object.mime_type, decoded.mime_type);
+                    assert_eq!(object.filename, decoded.filename);
+                    assert_eq!(object.description, decoded.description);
+                    assert_eq!(object.data, decoded.data);
+                }
+
+                let mut data_out = Vec::new();
+                encode(
+                    &mut data_out,
+                    &Content::EncapsulatedObject(object.clone()),
+                    tag::Id3v23,
+                    *encoding,
+                )
+                .unwrap();
+                assert_eq!(data, data_out);
+            }
+        }
+    }
+
+    #[test]
+    fn test_priv() {
+        assert!(decode("PRIV", tag::Id3v24, &[][..]).is_err());
+
+        let private = Private {
+            owner_identifier: "WM/MediaClassPrimaryID".to_string(),
+            data: vec![0xBC, 0x7D, 0x60, 0xD1],
+        };
+        let mut data = Vec::new();
+        data.extend(b"WM/MediaClassPrimaryID\0");
+        data.extend(&private.data);
+        assert_eq!(
+            private,
+            *decode("PRIV", tag::Id3v24, &data[..])
+                .unwrap()
+                .private()
+                .unwrap()
+        );
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::Private(private),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_chap() {
+        assert!(decode("CHAP", tag::Id3v24, &[][..]).is_err());
+        assert!(decode("CHAP", tag::Id3v24, &b"ch1\0\0\0\0\0"[..]).is_err());
+
+        let chapter = Chapter {
+            element_id: "ch1".to_string(),
+            start_time: 1000,
+            end_time: 2000,
+            start_offset: None,
+            end_offset: Some(0x0102),
+            frames: vec![Frame::with_content(
+                "TIT2",
+                Content::Text("Chapter 1".to_string()),
+            )],
+        };
+        for version in &[tag::Id3v23, tag::Id3v24] {
+            let mut data = Vec::new();
+            data.extend(b"ch1\0");
+            data.extend(&[0, 0, 0x03, 0xE8, 0, 0, 0x07, 0xD0]);
+            data.extend(&[0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0x01, 0x02]);
+            super::super::encode(
+                &mut data,
+                &chapter.frames[0],
+                *version,
+                false,
+                None,
+                &Ciphers::new(),
+            )
+            .unwrap();
+
+            let content = decode("CHAP", *version, &data[..]).unwrap();
+            let decoded = content.chapter().unwrap();
+            assert_eq!(chapter.element_id, decoded.element_id);
+            assert_eq!(chapter.start_time, decoded.start_time);
+            assert_eq!(chapter.end_time, decoded.end_time);
+            assert_eq!(chapter.start_offset, decoded.start_offset);
+            assert_eq!(chapter.end_offset, decoded.end_offset);
+            assert_eq!(chapter.frames, decoded.frames);
+            assert_eq!(Some("Chapter 1"), decoded.frames[0].content().text());
+
+            let mut data_out = Vec::new();
+            encode(
+                &mut data_out,
+                &Content::Chapter(chapter.clone()),
+                *version,
+                Encoding::UTF16,
+            )
+            .unwrap();
+            assert_eq!(data, data_out);
+        }
+    }
+
+    #[test]
+    fn test_ctoc() {
+        assert!(decode("CTOC", tag::Id3v24, &[][..]).is_err());
+        assert!(decode("CTOC", tag::Id3v24, &b"toc\0\x03\x02ch1\0"[..]).is_err());
+
+        let toc = TableOfContents {
+            element_id: "toc".to_string(),
+            top_level: true,
+            ordered: true,
+            elements: vec!["ch1".to_string(), "ch2".to_string()],
+            frames: Vec::new(),
+        };
+        let data = b"toc\0\x03\x02ch1\0ch2\0".to_vec();
+        let content = decode("CTOC", tag::Id3v24, &data[..]).unwrap();
+        let decoded = content.table_of_contents().unwrap();
+        assert_eq!(toc.element_id, decoded.element_id);
+        assert_eq!(toc.top_level, decoded.top_level);
+        assert_eq!(toc.ordered, decoded.ordered);
+        assert_eq!(toc.elements, decoded.elements);
+        assert!(decoded.frames.is_empty());
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::TableOfContents(toc),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_mcdi() {
+        assert_eq!(
+            Some(&[0xFF][..]),
+            decode("MCDI", tag::Id3v24, &[0xFF][..]).unwrap().unknown()
+        );
+
+        let mcdi = MusicCdIdentifier::from_offsets(1, &[150, 15363], 32314);
+        let data = vec![
+            0x00, 0x1A, 0x01, 0x02, // header
+            0x00, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // track 1
+            0x00, 0x10, 0x02, 0x00, 0x00, 0x00, 0x3B, 0x6D, // track 2
+            0x00, 0x10, 0xAA, 0x00, 0x00, 0x00, 0x7D, 0xA4, // lead-out
+        ];
+        for id in &["MCDI", "MCI"] {
+            assert_eq!(
+                mcdi,
+                *decode(id, tag::Id3v24, &data[..])
+                    .unwrap()
+                    .music_cd_identifier()
+                    .unwrap()
+            );
         }
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::MusicCdIdentifier(mcdi),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+
+        let mut data = data;
+        data[20..28].copy_from_slice(&[0x00, 0x10, 0xAA, 0x00, 0xFF, 0xFF, 0xFF, 0xFF]);
+        let content = decode("MCDI", tag::Id3v24, &data[..]).unwrap();
+        let mcdi = content.music_cd_identifier().unwrap();
+        assert_eq!(Some(u32::MAX), mcdi.lead_out_offset());
+        assert_eq!(vec![150, 15363], mcdi.track_offsets());
+    }
+
+    #[test]
+    fn test_rva2() {
+        assert!(decode("RVA2", tag::Id3v24, &[][..]).is_err());
+        assert!(decode("RVA2", tag::Id3v24, &b"track\0\x01\xfc"[..]).is_err());
+        assert!(decode("RVA2", tag::Id3v24, &b"track\0\x01\xfc\x00\x10\x80"[..]).is_err());
+
+        let rva2 = RelativeVolumeAdjustment {
+            identification: "track".to_string(),
+            channels: vec![
+                ChannelAdjustment {
+                    channel_type: ChannelType::MasterVolume,
+                    volume_adjustment: -1024,
+                    peak_bits: 16,
+                    peak: vec![0x40, 0x00],
+                },
+                ChannelAdjustment::with_gain(ChannelType::Subwoofer, 1.5),
+            ],
+        };
+        let data = b"track\0\x01\xfc\x00\x10\x40\x00\x08\x03\x00\x00".to_vec();
+        let content = decode("RVA2", tag::Id3v24, &data[..]).unwrap();
+        let decoded = content.relative_volume_adjustment().unwrap();
+        assert_eq!(rva2.channels, decoded.channels);
+        let master = decoded.channel(ChannelType::MasterVolume).unwrap();
+        assert_eq!(-2.0, master.gain());
+        assert_eq!(Some(0.5), master.peak_amplitude());
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::RelativeVolumeAdjustment(rva2),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_rvad() {
+        assert!(decode("RVAD", tag::Id3v23, &[][..]).is_err());
+        assert!(decode("RVAD", tag::Id3v23, &[0x03, 0x00][..]).is_err());
+        assert!(decode("RVAD", tag::Id3v23, &[0x03, 0x10, 0x00, 0x01][..]).is_err());
+        // Values that do not fit are kept as is.
+        let mut data = vec![0x03, 0x48];
+        data.extend(&[0x01; 4 * 9]);
+        assert_eq!(
+            Some(&data[..]),
+            decode("RVAD", tag::Id3v23, &data[..]).unwrap().unknown()
+        );
+        let mut data = vec![0x03, 0x40];
+        data.extend(&[0x80, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend(&[0x00; 3 * 8]);
+        assert_eq!(
+            Some(&data[..]),
+            decode("RVAD", tag::Id3v23, &data[..]).unwrap().unknown()
+        );
+        data[2] = 0x7f;
+        let content = decode("RVAD", tag::Id3v23, &data[..]).unwrap();
+        let rvad = content.volume_adjustment().unwrap();
+        assert_eq!(0x7f00_0000_0000_0000, rvad.right.adjustment);
+        assert!(rvad.right.gain().is_finite());
+        let change = VolumeChange {
+            adjustment: i64::MIN,
+            peak: 0,
+        };
+        assert!(change.gain() < 0.0);
+
+        let rvad = VolumeAdjustment {
+            bits: 16,
+            right: VolumeChange {
+                adjustment: 256,
+                peak: 0x8000,
+            },
+            left: VolumeChange {
+                adjustment: -256,
+                peak: 0x7000,
+            },
+            back_right: None,
+            back_left: None,
+            centre: Some(VolumeChange {
+                adjustment: 1,
+                peak: 0,
+            }),
+            bass: None,
+        };
+        let data = vec![
+            0x11, 0x10, // flags, bits
+            0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x70, 0x00, // right, left
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // back
+            0x00, 0x01, 0x00, 0x00, // centre
+        ];
+        for id in &["RVAD", "RVA"] {
+            let mut expected = rvad.clone();
+            expected.back_right = Some(VolumeChange::default());
+            expected.back_left = Some(VolumeChange::default());
+            assert_eq!(
+                expected,
+                *decode(id, tag::Id3v23, &data[..])
+                    .unwrap()
+                    .volume_adjustment()
+                    .unwrap()
+            );
+        }
+        assert!((rvad.right.gain() - 6.0206).abs() < 0.001);
+        assert!((rvad.left.gain() + 6.0206).abs() < 0.001);
+        assert_eq!(256, VolumeChange::with_gain(rvad.right.gain()).adjustment);
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::VolumeAdjustment(rvad),
+            tag::Id3v23,
+            Encoding::UTF16,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_etco() {
+        assert!(decode("ETCO", tag::Id3v24, &[][..]).is_err());
+        assert!(decode("ETCO", tag::Id3v24, &[0x03][..]).is_err());
+        assert!(decode("ETCO", tag::Id3v24, &[0x02, 0x02, 0x00][..]).is_err());
+
+        let etco = EventTimingCodes {
+            timestamp_format: TimestampFormat::MS,
+            events: vec![
+                (0, EventType::IntroStart),
+                (15_000, EventType::VerseStart),
+                (60_000, EventType::NotPredefinedSynch(3)),
+                (180_000, EventType::AudioEnd),
+            ],
+        };
+        let data = vec![
+            0x02, // timestamp format
+            0x02, 0x00, 0x00, 0x00, 0x00, // intro start
+            0x06, 0x00, 0x00, 0x3A, 0x98, // verse start
+            0xE3, 0x00, 0x00, 0xEA, 0x60, // not predefined synch 3
+            0xFD, 0x00, 0x02, 0xBF, 0x20, // audio end
+        ];
+        for id in &["ETCO", "ETC"] {
+            assert_eq!(
+                etco,
+                *decode(id, tag::Id3v24, &data[..])
+                    .unwrap()
+                    .event_timing_codes()
+                    .unwrap()
+            );
+        }
+        assert_eq!(EventType::Undefined(0x42), EventType::from(0x42));
+        assert_eq!(0x42u8, EventType::Undefined(0x42).into());
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::EventTimingCodes(etco),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_sytc() {
+        assert!(decode("SYTC", tag::Id3v24, &[][..]).is_err());
+        assert!(decode("SYTC", tag::Id3v24, &[0x01, 0xFF][..]).is_err());
+
+        let sytc = SynchronisedTempoCodes {
+            timestamp_format: TimestampFormat::MPEG,
+            tempo: vec![(0, 0), (1_000, 120), (5_000, 300)],
+        };
+        let data = vec![
+            0x01, // timestamp format
+            0x00, 0x00, 0x00, 0x00, 0x00, // beat-free
+            0x78, 0x00, 0x00, 0x03, 0xE8, // 120 bpm
+            0xFF, 0x2D, 0x00, 0x00, 0x13, 0x88, // 300 bpm
+        ];
+        for id in &["SYTC", "STC"] {
+            assert_eq!(
+                sytc,
+                *decode(id, tag::Id3v24, &data[..])
+                    .unwrap()
+                    .synchronised_tempo_codes()
+                    .unwrap()
+            );
+        }
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::SynchronisedTempoCodes(sytc),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_mllt() {
+        assert!(decode("MLLT", tag::Id3v24, &[0x00; 9][..]).is_err());
+        assert!(decode("MLLT", tag::Id3v24, &[0x00; 10][..]).is_err());
+
+        let mllt = MpegLocationLookupTable {
+            frames_between_reference: 10,
+            bytes_between_reference: 4_180,
+            millis_between_reference: 261,
+            bits_for_bytes: 12,
+            bits_for_millis: 8,
+            references: vec![
+                MpegLocationLookupTableReference {
+                    deviate_bytes: 0xABC,
+                    deviate_millis: 0x12,
+                },
+                MpegLocationLookupTableReference {
+                    deviate_bytes: 0x001,
+                    deviate_millis: 0xFF,
+                },
+                MpegLocationLookupTableReference {
+                    deviate_bytes: 0xFFF,
+                    deviate_millis: 0x00,
+                },
+            ],
+        };
+        let data = vec![
+            0x00, 0x0A, // frames between reference
+            0x00, 0x10, 0x54, // bytes between reference
+            0x00, 0x01, 0x05, // milliseconds between reference
+            0x0C, 0x08, // bits for deviations
+            0xAB, 0xC1, 0x20, 0x01, 0xFF, 0xFF, 0xF0, 0x00, // references, padded
+        ];
+        for id in &["MLLT", "MLL"] {
+            assert_eq!(
+                mllt,
+                *decode(id, tag::Id3v24, &data[..])
+                    .unwrap()
+                    .mpeg_location_lookup_table()
+                    .unwrap()
+            );
+        }
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::MpegLocationLookupTable(mllt.clone()),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+
+        // Round trip odd bit widths whose sum is a multiple of four.
+        let mut mllt = mllt;
+        mllt.bits_for_bytes = 27;
+        mllt.bits_for_millis = 5;
+        mllt.references = (0..7)
+            .map(|i| MpegLocationLookupTableReference {
+                deviate_bytes: 0x07FF_FFFF >> i,
+                deviate_millis: i,
+            })
+            .collect();
+        let content = Content::MpegLocationLookupTable(mllt);
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(content, decode("MLLT", tag::Id3v24, &data_out[..]).unwrap());
+
+        // References of four bits fit in the padding of the last byte.
+        let mut mllt = content.mpeg_location_lookup_table().unwrap().clone();
+        mllt.bits_for_bytes = 2;
+        mllt.bits_for_millis = 2;
+        for num_references in 1..=4 {
+            mllt.references = (0..num_references)
+                .map(|i| MpegLocationLookupTableReference {
+                    deviate_bytes: 3 - i % 4,
+                    deviate_millis: i % 3 + 1,
+                })
+                .collect();
+            let content = Content::MpegLocationLookupTable(mllt.clone());
+            let mut data_out = Vec::new();
+            encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+            assert_eq!(10 + (num_references as usize).div_ceil(2), data_out.len());
+            assert_eq!(content, decode("MLLT", tag::Id3v24, &data_out[..]).unwrap());
+        }
+
+        let mut invalid = content.mpeg_location_lookup_table().unwrap().clone();
+        invalid.bits_for_millis = 4;
+        let content = Content::MpegLocationLookupTable(invalid);
+        assert!(encode(Vec::new(), &content, tag::Id3v24, Encoding::UTF8).is_err());
+    }
+
+    #[test]
+    fn test_poss() {
+        assert!(decode("POSS", tag::Id3v24, &[0x02][..]).is_err());
+        assert!(decode("POSS", tag::Id3v24, &[0x00, 0x01][..]).is_err());
+
+        let poss = PositionSynchronisation {
+            timestamp_format: TimestampFormat::MS,
+            position: 90_000,
+        };
+        let data = vec![0x02, 0x00, 0x01, 0x5F, 0x90];
+        assert_eq!(
+            poss,
+            *decode("POSS", tag::Id3v24, &data[..])
+                .unwrap()
+                .position_synchronisation()
+                .unwrap()
+        );
+        assert_eq!(
+            poss,
+            *decode("POSS", tag::Id3v24, &[0x02, 0x01, 0x5F, 0x90][..])
+                .unwrap()
+                .position_synchronisation()
+                .unwrap()
+        );
+
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::PositionSynchronisation(poss),
+            tag::Id3v24,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_user() {
+        assert!(decode("USER", tag::Id3v24, &[][..]).is_err());
+
+        let user = TermsOfUse {
+            lang: "eng".to_string(),
+            text: "All rights reserved".to_string(),
+        };
+        for encoding in &[Encoding::Latin1, Encoding::UTF8, Encoding::UTF16] {
+            let mut data = vec![*encoding as u8];
+            data.extend(b"eng".iter().cloned());
+            data.extend(bytes_for_encoding(&user.text, *encoding));
+
+            let content = decode("USER", tag::Id3v24, &data[..]).unwrap();
+            assert_eq!(user.text, content.terms_of_use().unwrap().text);
+
+            let mut data_out = Vec::new();
+            encode(&mut data_out, &content, tag::Id3v24, *encoding).unwrap();
+            assert_eq!(data, data_out);
+        }
+    }
+
+    #[test]
+    fn test_owne() {
+        assert!(decode("OWNE", tag::Id3v24, &[0x00, b'U', b'S', b'D', 0x00][..]).is_err());
+
+        let owne = Ownership {
+            price_paid: "USD0.99".to_string(),
+            date_of_purchase: "20190103".to_string(),
+            seller: "Example Store".to_string(),
+        };
+        for encoding in &[Encoding::Latin1, Encoding::UTF16] {
+            let mut data = vec![*encoding as u8];
+            data.extend(b"USD0.99".iter().cloned());
+            data.push(0);
+            data.extend(b"20190103".iter().cloned());
+            data.extend(bytes_for_encoding(&owne.seller, *encoding));
+
+            let content = decode("OWNE", tag::Id3v24, &data[..]).unwrap();
+            assert_eq!(owne, *content.ownership().unwrap());
+
+            let mut data_out = Vec::new();
+            encode(&mut data_out, &content, tag::Id3v24, *encoding).unwrap();
+            assert_eq!(data, data_out);
+        }
+
+        let mut invalid = owne;
+        invalid.date_of_purchase = "2019-01-03".to_string();
+        let content = Content::Ownership(invalid);
+        assert!(encode(Vec::new(), &content, tag::Id3v24, Encoding::UTF8).is_err());
+    }
+
+    #[test]
+    fn test_comr() {
+        let mut comr = Commercial {
+            price: "USD0.99/EUR0.89".to_string(),
+            valid_until: "20201231".to_string(),
+            contact_url: "http://example.com".to_string(),
+            received_as: ReceivedAs::FileOverInternet,
+            seller: "Example Store".to_string(),
+            description: "Single".to_string(),
+            seller_logo: None,
+        };
+        for encoding in &[Encoding::Latin1, Encoding::UTF16] {
+            let mut data = vec![*encoding as u8];
+            data.extend(b"USD0.99/EUR0.89".iter().cloned());
+            data.push(0);
+            data.extend(b"20201231http://example.com".iter().cloned());
+            data.push(0);
+            data.push(0x03);
+            data.extend(bytes_for_encoding(&comr.seller, *encoding));
+            data.extend(delim_for_encoding(*encoding));
+            data.extend(bytes_for_encoding(&comr.description, *encoding));
+            data.extend(delim_for_encoding(*encoding));
+
+            let content = decode("COMR", tag::Id3v24, &data[..]).unwrap();
+            assert_eq!(comr, *content.commercial().unwrap());
+            let mut data_out = Vec::new();
+            encode(&mut data_out, &content, tag::Id3v24, *encoding).unwrap();
+            assert_eq!(data, data_out);
+
+            // The description terminator may be omitted when there is no logo.
+            let unterminated = &data[..data.len() - delim_for_encoding(*encoding).len()];
+            assert_eq!(content, decode("COMR", tag::Id3v24, unterminated).unwrap());
+        }
+
+        comr.seller_logo = Some(SellerLogo {
+            mime_type: "image/png".to_string(),
+            data: vec![0x89, b'P', b'N', b'G'],
+        });
+        let content = Content::Commercial(comr);
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(content, decode("COMR", tag::Id3v24, &data_out[..]).unwrap());
+        assert_eq!(b"image/png\0\x89PNG", &data_out[data_out.len() - 14..]);
+    }
+
+    #[test]
+    fn test_link() {
+        assert!(decode("LINK", tag::Id3v24, &b"API"[..]).is_err());
+        assert!(decode("LINK", tag::Id3v24, &b"APIChttp://example.com"[..]).is_err());
+
+        let link = LinkedInformation {
+            frame_identifier: "APIC".to_string(),
+            url: "http://example.com/booklet.mp3".to_string(),
+            additional_data: vec!["Booklet".to_string()],
+        };
+        let data = b"APIChttp://example.com/booklet.mp3\0Booklet".to_vec();
+        let content = decode("LINK", tag::Id3v24, &data[..]).unwrap();
+        assert_eq!(link, *content.linked_information().unwrap());
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(data, data_out);
+
+        // ID3v2.2 frames use three character identifiers.
+        let data_v2 = b"PIChttp://example.com/booklet.mp3\0Booklet".to_vec();
+        assert_eq!(content, decode("LNK", tag::Id3v22, &data_v2[..]).unwrap());
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v22, Encoding::UTF8).unwrap();
+        assert_eq!(data_v2, data_out);
+
+        let link = LinkedInformation {
+            frame_identifier: "COMM".to_string(),
+            url: "http://example.com".to_string(),
+            additional_data: vec!["engnote".to_string(), "extra".to_string()],
+        };
+        let content = Content::LinkedInformation(link);
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v23, Encoding::UTF8).unwrap();
+        assert_eq!(content, decode("LINK", tag::Id3v23, &data_out[..]).unwrap());
+
+        let link = LinkedInformation {
+            frame_identifier: "TIT2".to_string(),
+            url: "http://example.com".to_string(),
+            additional_data: Vec::new(),
+        };
+        let content = Content::LinkedInformation(link);
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(b"TIT2http://example.com\0".to_vec(), data_out);
+        assert_eq!(content, decode("LINK", tag::Id3v24, &data_out[..]).unwrap());
+    }
+
+    #[test]
+    fn test_aspi() {
+        assert!(decode("ASPI", tag::Id3v24, &[0x00; 10][..]).is_err());
+        let mut truncated = vec![0x00; 8];
+        truncated.extend(&[0x00, 0x02, 0x10, 0x00, 0x01]);
+        assert!(decode("ASPI", tag::Id3v24, &truncated[..]).is_err());
+
+        let aspi = AudioSeekPointIndex {
+            indexed_data_start: 0x1000,
+            indexed_data_length: 0x0010_0000,
+            bits_per_index_point: 16,
+            index_points: vec![0x0000, 0x4000, 0x8123],
+        };
+        let data = vec![
+            0x00, 0x00, 0x10, 0x00, // indexed data start
+            0x00, 0x10, 0x00, 0x00, // indexed data length
+            0x00, 0x03, 0x10, // number of index points, bits per index point
+            0x00, 0x00, 0x40, 0x00, 0x81, 0x23, // index points
+        ];
+        let content = decode("ASPI", tag::Id3v24, &data[..]).unwrap();
+        assert_eq!(aspi, *content.audio_seek_point_index().unwrap());
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(data, data_out);
+
+        let mut aspi = aspi;
+        aspi.bits_per_index_point = 8;
+        assert!(encode(
+            Vec::new(),
+            &Content::AudioSeekPointIndex(aspi.clone()),
+            tag::Id3v24,
+            Encoding::UTF8
+        )
+        .is_err());
+        aspi.index_points = vec![0x00, 0x40, 0x81];
+        let content = Content::AudioSeekPointIndex(aspi);
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(14, data_out.len());
+        assert_eq!(content, decode("ASPI", tag::Id3v24, &data_out[..]).unwrap());
+    }
+
+    #[test]
+    fn test_seek() {
+        assert!(decode("SEEK", tag::Id3v24, &[0x00, 0x01][..]).is_err());
+        let data = vec![0x00, 0x01, 0x00, 0x00];
+        let content = decode("SEEK", tag::Id3v24, &data[..]).unwrap();
+        assert_eq!(Some(0x0001_0000), content.seek_offset());
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_equ2() {
+        assert!(decode("EQU2", tag::Id3v24, &[0x00, 0x00, 0x01][..]).is_err());
+
+        let equ2 = Equalisation {
+            interpolation_method: InterpolationMethod::Linear,
+            identification: "car".to_string(),
+            adjustments: vec![(200, -1024), (2_000, 0), (20_000, 512)],
+        };
+        let data = vec![
+            0x01, b'c', b'a', b'r', 0x00, // interpolation method, identification
+            0x00, 0xC8, 0xFC, 0x00, // 100 Hz, -2 dB
+            0x07, 0xD0, 0x00, 0x00, // 1 kHz, 0 dB
+            0x4E, 0x20, 0x02, 0x00, // 10 kHz, 1 dB
+        ];
+        let content = decode("EQU2", tag::Id3v24, &data[..]).unwrap();
+        let decoded = content.equalisation().unwrap();
+        assert_eq!(equ2.interpolation_method, decoded.interpolation_method);
+        assert_eq!(equ2.adjustments, decoded.adjustments);
+
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_equa() {
+        assert!(decode("EQUA", tag::Id3v23, &[][..]).is_err());
+        assert!(decode("EQUA", tag::Id3v23, &[0x00][..]).is_err());
+        // Adjustments that do not fit are kept as is.
+        let data = [0x48, 0x00, 0x64, 0x01, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            Some(&data[..]),
+            decode("EQUA", tag::Id3v23, &data[..]).unwrap().unknown()
+        );
+        let mut data = vec![0x40, 0x00, 0x64, 0x80, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            Some(&data[..]),
+            decode("EQUA", tag::Id3v23, &data[..]).unwrap().unknown()
+        );
+        data[3] = 0x7F;
+        let content = decode("EQUA", tag::Id3v23, &data[..]).unwrap();
+        assert_eq!(
+            vec![(100, -0x7F00_0000_0000_0000)],
+            content.legacy_equalisation().unwrap().adjustments
+        );
+
+        let equa = LegacyEqualisation {
+            bits: 16,
+            adjustments: vec![(100, -256), (1_000, 0), (10_000, 256)],
+        };
+        let data = vec![
+            0x10, // bits
+            0x00, 0x64, 0x01, 0x00, // 100 Hz, decrement
+            0x03, 0xE8, 0x00, 0x00, // 1 kHz
+            0xA7, 0x10, 0x01, 0x00, // 10 kHz, increment
+        ];
+        for id in &["EQUA", "EQU"] {
+            assert_eq!(
+                equa,
+                *decode(id, tag::Id3v23, &data[..])
+                    .unwrap()
+                    .legacy_equalisation()
+                    .unwrap()
+            );
+        }
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::LegacyEqualisation(equa.clone()),
+            tag::Id3v23,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+
+        let equ2 = Equalisation::from(&equa);
+        assert_eq!("", equ2.identification);
+        assert_eq!(
+            vec![(200, -3083), (2_000, 0), (20_000, 3083)],
+            equ2.adjustments
+        );
+        assert_eq!(equa, LegacyEqualisation::from(&equ2));
+    }
+
+    #[test]
+    fn test_rvrb() {
+        assert!(decode("RVRB", tag::Id3v23, &[0x00; 11][..]).is_err());
+        let data = vec![
+            0x00, 0x64, 0x00, 0x78, 0x03, 0x04, 0x80, 0x10, 0x80, 0x20, 0x40, 0x50,
+        ];
+        let reverb = Reverb {
+            reverb_left: 100,
+            reverb_right: 120,
+            bounces_left: 3,
+            bounces_right: 4,
+            feedback_left_to_left: 0x80,
+            feedback_left_to_right: 0x10,
+            feedback_right_to_right: 0x80,
+            feedback_right_to_left: 0x20,
+            premix_left_to_right: 0x40,
+            premix_right_to_left: 0x50,
+        };
+        for id in &["RVRB", "REV"] {
+            assert_eq!(
+                reverb,
+                *decode(id, tag::Id3v23, &data[..])
+                    .unwrap()
+                    .reverb()
+                    .unwrap()
+            );
+        }
+        let mut data_out = Vec::new();
+        encode(
+            &mut data_out,
+            &Content::Reverb(reverb),
+            tag::Id3v23,
+            Encoding::UTF8,
+        )
+        .unwrap();
+        assert_eq!(data, data_out);
+    }
+
+    #[test]
+    fn test_rbuf() {
+        assert!(decode("RBUF", tag::Id3v23, &[0x00; 3][..]).is_err());
+        let rbuf = RecommendedBufferSize {
+            buffer_size: 0x01_0000,
+            embedded_info: true,
+            offset_to_next_tag: None,
+        };
+        let data = [0x01, 0x00, 0x00, 0x01];
+        for id in &["RBUF", "BUF"] {
+            assert_eq!(
+                rbuf,
+                *decode(id, tag::Id3v23, &data[..])
+                    .unwrap()
+                    .recommended_buffer_size()
+                    .unwrap()
+            );
+        }
+
+        let rbuf = RecommendedBufferSize {
+            buffer_size: 0x8000,
+            embedded_info: false,
+            offset_to_next_tag: Some(0x0001_2345),
+        };
+        let content = Content::RecommendedBufferSize(rbuf);
+        let mut data_out = Vec::new();
+        encode(&mut data_out, &content, tag::Id3v24, Encoding::UTF8).unwrap();
+        assert_eq!(
+            vec![0x00, 0x80, 0x00, 0x00, 0x00, 0x01, 0x23, 0x45],
+            data_out
+        );
+        assert_eq!(content, decode("RBUF", tag::Id3v24, &data_out[..]).unwrap());
+    }
+
+    #[test]
+    fn test_rva2_rvad_conversion() {
+        let rva2 = RelativeVolumeAdjustment {
+            identification: "track".to_string(),
+            channels: vec![ChannelAdjustment {
+                channel_type: ChannelType::MasterVolume,
+                volume_adjustment: -3072,
+                peak_bits: 16,
+                peak: vec![0x40, 0x00],
+            }],
+        };
+        let rvad = VolumeAdjustment::from(&rva2);
+        assert_eq!(16, rvad.bits);
+        assert_eq!(rvad.right, rvad.left);
+        assert!((rvad.right.gain() + 6.0).abs() < 0.02);
+        assert_eq!(0x4000, rvad.right.peak);
+        assert!(rvad.centre.is_none());
+
+        let converted = RelativeVolumeAdjustment::from(&rvad);
+        assert_eq!("track", converted.identification);
+        assert_eq!(1, converted.channels.len());
+        let master = converted.channel(ChannelType::MasterVolume).unwrap();
+        assert!((master.gain() + 6.0).abs() < 0.02);
+        assert_eq!(Some(0.5), master.peak_amplitude());
     }
 }
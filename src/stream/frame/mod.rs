@@ -1,7 +1,8 @@
 use crate::frame::Content;
-use crate::frame::Frame;
+use crate::frame::{Ciphers, Frame};
 use crate::stream::unsynch;
 use crate::tag;
+use crate::{Error, ErrorKind};
 use flate2::read::ZlibDecoder;
 use std::io;
 
@@ -14,44 +15,155 @@ pub fn decode(
     mut reader: impl io::Read,
     version: tag::Version,
     unsynchronization: bool,
+    ciphers: &Ciphers,
 ) -> crate::Result<Option<(usize, Frame)>> {
     match version {
         tag::Id3v22 => unreachable!(), //We handled this already
-        tag::Id3v23 => v3::decode(&mut reader, unsynchronization),
-        tag::Id3v24 => v4::decode(&mut reader),
+        tag::Id3v23 => v3::decode(&mut reader, unsynchronization, ciphers),
+        tag::Id3v24 => v4::decode(&mut reader, ciphers),
     }
 }
 
 pub fn decode_content(
     reader: impl io::Read,
     id: &str,
+    version: tag::Version,
     compression: bool,
     unsynchronisation: bool,
 ) -> crate::Result<Content> {
     let result = if unsynchronisation {
         let reader_unsynch = unsynch::Reader::new(reader);
         if compression {
-            content::decode(id, ZlibDecoder::new(reader_unsynch))
+            content::decode(id, version, ZlibDecoder::new(reader_unsynch))
         } else {
-            content::decode(id, reader_unsynch)
+            content::decode(id, version, reader_unsynch)
         }
     } else if compression {
-        content::decode(id, ZlibDecoder::new(reader))
+        content::decode(id, version, ZlibDecoder::new(reader))
     } else {
-        content::decode(id, reader)
+        content::decode(id, version, reader)
     };
     Ok(result?)
 }
 
+/// Decodes an encrypted frame.
+///
+/// If no cipher is registered for the encryption method, the frame holds the encrypted data as
+/// `Content::Unknown` and is marked as such.
+fn decode_encrypted_frame(
+    mut reader: impl io::Read,
+    id: &str,
+    version: tag::Version,
+    encryption_method: u8,
+    compression: bool,
+    unsynchronisation: bool,
+    ciphers: &Ciphers,
+) -> crate::Result<Frame> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    if unsynchronisation {
+        unsynch::decode_vec(&mut data);
+    }
+    match ciphers.get(encryption_method) {
+        Some(cipher) => {
+            let data = cipher.decrypt(id, &data)?;
+            let content = decode_content(&data[..], id, version, compression, false)?;
+            Ok(Frame::with_content(id, content))
+        }
+        None => {
+            let mut frame = Frame::with_content(id, Content::Unknown(data));
+            frame.set_encrypted_content();
+            Ok(frame)
+        }
+    }
+}
+
+/// Encrypts the encoded content of a frame.
+///
+/// Frames that could not be decrypted hold data that is encrypted already.
+fn encrypt_content(
+    frame: &Frame,
+    encryption_method: u8,
+    data: Vec<u8>,
+    ciphers: &Ciphers,
+) -> crate::Result<Vec<u8>> {
+    if frame.has_encrypted_content() {
+        return Ok(data);
+    }
+    match ciphers.get(encryption_method) {
+        Some(cipher) => cipher.encrypt(frame.id(), &data),
+        None => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "no cipher is registered for the encryption method of a frame",
+        )),
+    }
+}
+
+/// Converts a frame whose layout differs between versions to the layout of the specified
+/// version. Returns None if the frame can be encoded as is.
+///
+/// Only the RVA2 frame with the identification "track" is converted to RVAD, the encoder omits the
+/// others. Only the EQU2 frame with an empty identification is converted to EQUA, since a tag may
+/// contain only one RVAD and EQUA frame.
+fn convert_for_version(frame: &Frame, version: tag::Version) -> Option<Frame> {
+    match (version, frame.content()) {
+        (tag::Id3v24, Content::VolumeAdjustment(rvad)) => Some(
+            frame.with_converted_content("RVA2", Content::RelativeVolumeAdjustment(rvad.into())),
+        ),
+        (tag::Id3v22, Content::RelativeVolumeAdjustment(rva2))
+        | (tag::Id3v23, Content::RelativeVolumeAdjustment(rva2))
+            if rva2.identification.eq_ignore_ascii_case("track") =>
+        {
+            let id = if version == tag::Id3v22 {
+                "RVA2"
+            } else {
+                "RVAD"
+            };
+            Some(frame.with_converted_content(id, Content::VolumeAdjustment(rva2.into())))
+        }
+        // ID3v2.2 RVA frames are mapped to RVA2.
+        (tag::Id3v23, Content::VolumeAdjustment(_)) if frame.id() != "RVAD" => {
+            Some(frame.with_converted_content("RVAD", frame.content().clone()))
+        }
+        (tag::Id3v22, Content::VolumeAdjustment(_)) if frame.id() != "RVA2" => {
+            Some(frame.with_converted_content("RVA2", frame.content().clone()))
+        }
+        (tag::Id3v24, Content::LegacyEqualisation(equa)) => {
+            Some(frame.with_converted_content("EQU2", Content::Equalisation(equa.into())))
+        }
+        // ID3v2.2 EQU frames are mapped to EQUA.
+        (tag::Id3v22, Content::Equalisation(equ2)) | (tag::Id3v23, Content::Equalisation(equ2))
+            if equ2.identification.is_empty() =>
+        {
+            Some(frame.with_converted_content("EQUA", Content::LegacyEqualisation(equ2.into())))
+        }
+        (tag::Id3v22, Content::LegacyEqualisation(_))
+        | (tag::Id3v23, Content::LegacyEqualisation(_))
+            if frame.id() != "EQUA" =>
+        {
+            Some(frame.with_converted_content("EQUA", frame.content().clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Encodes a frame.
+///
+/// If a compression threshold is set, frames with content of at least that many bytes are
+/// compressed. Compression is not supported by ID3v2.2.
 pub fn encode<W>(
     writer: &mut W,
     frame: &Frame,
     version: tag::Version,
     unsynchronization: bool,
+    compression_threshold: Option<usize>,
+    ciphers: &Ciphers,
 ) -> crate::Result<usize>
 where
     W: io::Write,
 {
+    let converted = convert_for_version(frame, version);
+    let frame = converted.as_ref().unwrap_or(frame);
     match version {
         tag::Id3v22 => v2::encode(writer, frame),
         tag::Id3v23 => {
@@ -64,7 +176,17 @@ where
                 v3::Flags::FILE_ALTER_PRESERVATION,
                 frame.file_alter_preservation(),
             );
-            v3::encode(writer, frame, flags, unsynchronization)
+            flags.set(v3::Flags::READ_ONLY, frame.read_only());
+            flags.set(v3::Flags::GROUPING_IDENTITY, frame.group_id().is_some());
+            flags.set(v3::Flags::ENCRYPTION, frame.encryption_method().is_some());
+            v3::encode(
+                writer,
+                frame,
+                flags,
+                unsynchronization,
+                compression_threshold,
+                ciphers,
+            )
         }
         tag::Id3v24 => {
             let mut flags = v4::Flags::empty();
@@ -77,7 +199,10 @@ where
                 v4::Flags::FILE_ALTER_PRESERVATION,
                 frame.file_alter_preservation(),
             );
-            v4::encode(writer, frame, flags)
+            flags.set(v4::Flags::READ_ONLY, frame.read_only());
+            flags.set(v4::Flags::GROUPING_IDENTITY, frame.group_id().is_some());
+            flags.set(v4::Flags::ENCRYPTION, frame.encryption_method().is_some());
+            v4::encode(writer, frame, flags, compression_threshold, ciphers)
         }
     }
 }
@@ -109,7 +234,7 @@ mod tests {
         data.push(encoding as u8);
         data.extend(string_to_utf16(text).into_iter());
 
-        let content = decode_content(&data[..], id, false, false).unwrap();
+        let content = decode_content(&data[..], id, tag::Id3v22, false, false).unwrap();
         let frame = Frame::with_content(id, content);
 
         let mut bytes = Vec::new();
@@ -118,7 +243,15 @@ mod tests {
         bytes.extend(data.into_iter());
 
         let mut writer = Vec::new();
-        encode(&mut writer, &frame, tag::Id3v22, false).unwrap();
+        encode(
+            &mut writer,
+            &frame,
+            tag::Id3v22,
+            false,
+            None,
+            &Ciphers::new(),
+        )
+        .unwrap();
         assert_eq!(writer, bytes);
     }
 
@@ -132,7 +265,7 @@ mod tests {
         data.push(encoding as u8);
         data.extend(string_to_utf16(text).into_iter());
 
-        let content = decode_content(&data[..], id, false, false).unwrap();
+        let content = decode_content(&data[..], id, tag::Id3v24, false, false).unwrap();
         let frame = Frame::with_content(id, content);
 
         let mut bytes = Vec::new();
@@ -142,7 +275,15 @@ mod tests {
         bytes.extend(data.into_iter());
 
         let mut writer = Vec::new();
-        encode(&mut writer, &frame, tag::Id3v23, false).unwrap();
+        encode(
+            &mut writer,
+            &frame,
+            tag::Id3v23,
+            false,
+            None,
+            &Ciphers::new(),
+        )
+        .unwrap();
         assert_eq!(writer, bytes);
     }
 
@@ -156,7 +297,7 @@ mod tests {
         data.push(encoding as u8);
         data.extend(text.bytes());
 
-        let content = decode_content(&data[..], id, false, false).unwrap();
+        let content = decode_content(&data[..], id, tag::Id3v24, false, false).unwrap();
         let mut frame = Frame::with_content(id, content);
         frame.set_tag_alter_preservation(true);
         frame.set_file_alter_preservation(true);
@@ -168,7 +309,103 @@ mod tests {
         bytes.extend(data.into_iter());
 
         let mut writer = Vec::new();
-        encode(&mut writer, &frame, tag::Id3v24, false).unwrap();
+        encode(
+            &mut writer,
+            &frame,
+            tag::Id3v24,
+            false,
+            None,
+            &Ciphers::new(),
+        )
+        .unwrap();
         assert_eq!(writer, bytes);
     }
+
+    #[test]
+    fn test_group_id() {
+        let mut frame = Frame::with_content("TALB", Content::Text("album".to_string()));
+        frame.set_group_id(Some(0x80));
+        for version in &[tag::Id3v23, tag::Id3v24] {
+            for unsynchronization in &[false, true] {
+                let mut bytes = Vec::new();
+                encode(
+                    &mut bytes,
+                    &frame,
+                    *version,
+                    *unsynchronization,
+                    None,
+                    &Ciphers::new(),
+                )
+                .unwrap();
+                let (size, decoded) =
+                    decode(&bytes[..], *version, *unsynchronization, &Ciphers::new())
+                        .unwrap()
+                        .unwrap();
+                assert_eq!(bytes.len(), size);
+                assert_eq!(frame, decoded);
+                assert_eq!(Some(0x80), decoded.group_id());
+            }
+        }
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut frame = Frame::with_content("TALB", Content::Text("album".to_string()));
+        frame.set_tag_alter_preservation(true);
+        frame.set_file_alter_preservation(true);
+        frame.set_read_only(true);
+        for version in &[tag::Id3v23, tag::Id3v24] {
+            let mut bytes = Vec::new();
+            encode(
+                &mut bytes,
+                &frame,
+                *version,
+                false,
+                Some(0),
+                &Ciphers::new(),
+            )
+            .unwrap();
+            let (_, decoded) = decode(&bytes[..], *version, false, &Ciphers::new())
+                .unwrap()
+                .unwrap();
+            let flags = decoded.flags();
+            assert!(flags.tag_alter_preservation);
+            assert!(flags.file_alter_preservation);
+            assert!(flags.read_only);
+            assert!(flags.compression);
+            assert_eq!(*version == tag::Id3v24, flags.data_length_indicator);
+            assert!(!flags.grouping_identity);
+            assert!(!flags.encryption);
+
+            let mut reencoded = Vec::new();
+            encode(
+                &mut reencoded,
+                &decoded,
+                *version,
+                false,
+                Some(0),
+                &Ciphers::new(),
+            )
+            .unwrap();
+            assert_eq!(bytes, reencoded);
+        }
+    }
+
+    #[test]
+    fn test_size_too_small_for_flags() {
+        // Compression in ID3v2.3 and the data length indicator in ID3v2.4 are followed by four
+        // bytes that must be included in the frame size.
+        for (version, flags) in &[(tag::Id3v23, [0x00, 0x80]), (tag::Id3v24, [0x00, 0x09])] {
+            let mut bytes = b"TALB\x00\x00\x00\x02".to_vec();
+            bytes.extend(flags);
+            bytes.extend(&[0x00; 8]);
+            match decode(&bytes[..], *version, false, &Ciphers::new()) {
+                Err(Error {
+                    kind: ErrorKind::Parsing,
+                    ..
+                }) => (),
+                _ => panic!("expected a parsing error"),
+            }
+        }
+    }
 }
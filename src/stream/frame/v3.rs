@@ -1,4 +1,4 @@
-use crate::frame::Frame;
+use crate::frame::{Ciphers, Frame, FrameFlags};
 use crate::stream::encoding::Encoding;
 use crate::stream::frame;
 use crate::stream::unsynch;
@@ -8,7 +8,7 @@ use bitflags::bitflags;
 use byteorder::{BigEndian, ByteOrder, ReadBytesExt, WriteBytesExt};
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
-use std::io;
+use std::io::{self, Write};
 use std::str;
 
 bitflags! {
@@ -25,6 +25,7 @@ bitflags! {
 pub fn decode(
     mut reader: impl io::Read,
     unsynchronisation: bool,
+    ciphers: &Ciphers,
 ) -> crate::Result<Option<(usize, Frame)>> {
     let mut frame_header = [0; 10];
     let nread = reader.read(&mut frame_header)?;
@@ -36,58 +37,122 @@ pub fn decode(
     let content_size = BigEndian::read_u32(&frame_header[4..8]) as usize;
     let flags = Flags::from_bits(BigEndian::read_u16(&frame_header[8..10]))
         .ok_or_else(|| Error::new(ErrorKind::Parsing, "unknown frame header flags are set"))?;
-    if flags.contains(Flags::ENCRYPTION) {
-        return Err(Error::new(
-            ErrorKind::UnsupportedFeature,
-            "encryption is not supported",
-        ));
-    } else if flags.contains(Flags::GROUPING_IDENTITY) {
-        return Err(Error::new(
-            ErrorKind::UnsupportedFeature,
-            "grouping identity is not supported",
-        ));
-    }
 
-    let read_size = if flags.contains(Flags::COMPRESSION) {
-        let _decompressed_size = reader.read_u32::<BigEndian>()?;
-        content_size - 4
+    let mut read_size = content_size;
+    let decompressed_size = if flags.contains(Flags::COMPRESSION) {
+        read_size = read_size.checked_sub(4).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode decompressed size",
+            )
+        })?;
+        Some(reader.read_u32::<BigEndian>()?)
     } else {
-        content_size
+        None
     };
-    let content = super::decode_content(
-        reader.take(read_size as u64),
-        id,
-        flags.contains(Flags::COMPRESSION),
-        unsynchronisation,
-    )?;
-    let frame = Frame::with_content(id, content);
+    let encryption_method = if flags.contains(Flags::ENCRYPTION) {
+        read_size = read_size.checked_sub(1).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode encryption method",
+            )
+        })?;
+        Some(reader.read_u8()?)
+    } else {
+        None
+    };
+    let group_id = if flags.contains(Flags::GROUPING_IDENTITY) {
+        read_size = read_size.checked_sub(1).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode group identifier",
+            )
+        })?;
+        Some(reader.read_u8()?)
+    } else {
+        None
+    };
+    let mut frame = match encryption_method {
+        Some(method) => super::decode_encrypted_frame(
+            reader.take(read_size as u64),
+            id,
+            tag::Id3v23,
+            method,
+            flags.contains(Flags::COMPRESSION),
+            unsynchronisation,
+            ciphers,
+        )?,
+        None => Frame::with_content(
+            id,
+            super::decode_content(
+                reader.take(read_size as u64),
+                id,
+                tag::Id3v23,
+                flags.contains(Flags::COMPRESSION),
+                unsynchronisation,
+            )?,
+        ),
+    };
+    frame.set_group_id(group_id);
+    frame.set_encryption_method(encryption_method);
+    frame.set_decoded_flags(
+        FrameFlags {
+            tag_alter_preservation: flags.contains(Flags::TAG_ALTER_PRESERVATION),
+            file_alter_preservation: flags.contains(Flags::FILE_ALTER_PRESERVATION),
+            read_only: flags.contains(Flags::READ_ONLY),
+            compression: flags.contains(Flags::COMPRESSION),
+            ..FrameFlags::default()
+        },
+        decompressed_size,
+    );
     Ok(Some((10 + content_size, frame)))
 }
 
 pub fn encode(
     mut writer: impl io::Write,
     frame: &Frame,
-    flags: Flags,
+    mut flags: Flags,
     unsynchronization: bool,
+    compression_threshold: Option<usize>,
+    ciphers: &Ciphers,
 ) -> crate::Result<usize> {
-    let (mut content_buf, comp_hint_delta, decompressed_size) = if flags
-        .contains(Flags::COMPRESSION)
-    {
+    let mut content_buf = Vec::new();
+    frame::content::encode(
+        &mut content_buf,
+        frame.content(),
+        tag::Id3v23,
+        Encoding::UTF16,
+    )?;
+    let encryption_method = if flags.contains(Flags::ENCRYPTION) {
+        frame.encryption_method()
+    } else {
+        None
+    };
+
+    // Frames that could not be decrypted are written as is.
+    let opaque = encryption_method.is_some() && frame.has_encrypted_content();
+    let compress = match compression_threshold {
+        _ if opaque => frame.flags().compression,
+        Some(threshold) => content_buf.len() >= threshold,
+        None => false,
+    };
+    flags.set(Flags::COMPRESSION, compress);
+    let decompressed_size = if compress && opaque {
+        Some(frame.data_length().unwrap_or(0) as usize)
+    } else if compress {
+        let size = content_buf.len();
         let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-        let content_size =
-            frame::content::encode(&mut encoder, frame.content(), tag::Id3v23, Encoding::UTF16)?;
-        let content_buf = encoder.finish()?;
-        (content_buf, 4, Some(content_size))
+        encoder.write_all(&content_buf)?;
+        content_buf = encoder.finish()?;
+        Some(size)
     } else {
-        let mut content_buf = Vec::new();
-        frame::content::encode(
-            &mut content_buf,
-            frame.content(),
-            tag::Id3v23,
-            Encoding::UTF16,
-        )?;
-        (content_buf, 0, None)
+        None
     };
+    let comp_hint_delta = if decompressed_size.is_some() { 4 } else { 0 };
+
+    if let Some(method) = encryption_method {
+        content_buf = super::encrypt_content(frame, method, content_buf, ciphers)?;
+    }
     if unsynchronization {
         unsynch::encode_vec(&mut content_buf);
     }
@@ -97,11 +162,23 @@ pub fn encode(
         assert_eq!(4, id.len());
         id
     })?;
-    writer.write_u32::<BigEndian>((content_buf.len() + comp_hint_delta) as u32)?;
+    let group_id = if flags.contains(Flags::GROUPING_IDENTITY) {
+        frame.group_id()
+    } else {
+        None
+    };
+    let extra_delta = encryption_method.iter().chain(group_id.iter()).count();
+    writer.write_u32::<BigEndian>((content_buf.len() + comp_hint_delta + extra_delta) as u32)?;
     writer.write_u16::<BigEndian>(flags.bits())?;
     if let Some(s) = decompressed_size {
         writer.write_u32::<BigEndian>(s as u32)?;
     }
+    if let Some(method) = encryption_method {
+        writer.write_u8(method)?;
+    }
+    if let Some(group_id) = group_id {
+        writer.write_u8(group_id)?;
+    }
     writer.write_all(&content_buf)?;
-    Ok(10 + comp_hint_delta + content_buf.len())
+    Ok(10 + comp_hint_delta + extra_delta + content_buf.len())
 }
@@ -1,4 +1,4 @@
-use crate::frame::Frame;
+use crate::frame::{Ciphers, Frame, FrameFlags};
 use crate::stream::encoding::Encoding;
 use crate::stream::frame;
 use crate::stream::unsynch;
@@ -8,7 +8,7 @@ use bitflags::bitflags;
 use byteorder::{BigEndian, ByteOrder, ReadBytesExt, WriteBytesExt};
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
-use std::io;
+use std::io::{self, Write};
 use std::str;
 
 bitflags! {
@@ -24,7 +24,10 @@ bitflags! {
     }
 }
 
-pub fn decode(mut reader: impl io::Read) -> crate::Result<Option<(usize, Frame)>> {
+pub fn decode(
+    mut reader: impl io::Read,
+    ciphers: &Ciphers,
+) -> crate::Result<Option<(usize, Frame)>> {
     let mut frame_header = [0; 10];
     let nread = reader.read(&mut frame_header)?;
     if nread < frame_header.len() || frame_header[0] == 0x00 {
@@ -34,58 +37,132 @@ pub fn decode(mut reader: impl io::Read) -> crate::Result<Option<(usize, Frame)>
     let content_size = unsynch::decode_u32(BigEndian::read_u32(&frame_header[4..8])) as usize;
     let flags = Flags::from_bits(BigEndian::read_u16(&frame_header[8..10]))
         .ok_or_else(|| Error::new(ErrorKind::Parsing, "unknown frame header flags are set"))?;
-    if flags.contains(Flags::ENCRYPTION) {
-        return Err(Error::new(
-            ErrorKind::UnsupportedFeature,
-            "encryption is not supported",
-        ));
-    } else if flags.contains(Flags::GROUPING_IDENTITY) {
-        return Err(Error::new(
-            ErrorKind::UnsupportedFeature,
-            "grouping identity is not supported",
-        ));
-    }
 
-    let read_size = if flags.contains(Flags::DATA_LENGTH_INDICATOR) {
-        let _decompressed_size = unsynch::decode_u32(reader.read_u32::<BigEndian>()?);
-        content_size - 4
+    let mut read_size = content_size;
+    let group_id = if flags.contains(Flags::GROUPING_IDENTITY) {
+        read_size = read_size.checked_sub(1).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode group identifier",
+            )
+        })?;
+        Some(reader.read_u8()?)
+    } else {
+        None
+    };
+    let encryption_method = if flags.contains(Flags::ENCRYPTION) {
+        read_size = read_size.checked_sub(1).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode encryption method",
+            )
+        })?;
+        Some(reader.read_u8()?)
     } else {
-        content_size
+        None
+    };
+    let data_length = if flags.contains(Flags::DATA_LENGTH_INDICATOR) {
+        read_size = read_size.checked_sub(4).ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parsing,
+                "insufficient data to decode data length indicator",
+            )
+        })?;
+        Some(unsynch::decode_u32(reader.read_u32::<BigEndian>()?))
+    } else {
+        None
     };
 
-    let content = super::decode_content(
-        reader.take(read_size as u64),
-        id,
-        flags.contains(Flags::COMPRESSION),
-        flags.contains(Flags::UNSYNCHRONISATION),
-    )?;
-    let frame = Frame::with_content(id, content);
+    let mut frame = match encryption_method {
+        Some(method) => super::decode_encrypted_frame(
+            reader.take(read_size as u64),
+            id,
+            tag::Id3v24,
+            method,
+            flags.contains(Flags::COMPRESSION),
+            flags.contains(Flags::UNSYNCHRONISATION),
+            ciphers,
+        )?,
+        None => Frame::with_content(
+            id,
+            super::decode_content(
+                reader.take(read_size as u64),
+                id,
+                tag::Id3v24,
+                flags.contains(Flags::COMPRESSION),
+                flags.contains(Flags::UNSYNCHRONISATION),
+            )?,
+        ),
+    };
+    frame.set_group_id(group_id);
+    frame.set_encryption_method(encryption_method);
+    frame.set_decoded_flags(
+        FrameFlags {
+            tag_alter_preservation: flags.contains(Flags::TAG_ALTER_PRESERVATION),
+            file_alter_preservation: flags.contains(Flags::FILE_ALTER_PRESERVATION),
+            read_only: flags.contains(Flags::READ_ONLY),
+            compression: flags.contains(Flags::COMPRESSION),
+            unsynchronisation: flags.contains(Flags::UNSYNCHRONISATION),
+            data_length_indicator: flags.contains(Flags::DATA_LENGTH_INDICATOR),
+            ..FrameFlags::default()
+        },
+        data_length,
+    );
     Ok(Some((10 + content_size, frame)))
 }
 
-pub fn encode(mut writer: impl io::Write, frame: &Frame, flags: Flags) -> crate::Result<usize> {
-    let (mut content_buf, comp_hint_delta, decompressed_size) =
-        if flags.contains(Flags::COMPRESSION) {
-            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
-            let content_size =
-                frame::content::encode(&mut encoder, frame.content(), tag::Id3v24, Encoding::UTF8)?;
-            let content_buf = encoder.finish()?;
-            let cd = if flags.contains(Flags::DATA_LENGTH_INDICATOR) {
-                4
-            } else {
-                0
-            };
-            (content_buf, cd, Some(content_size))
+pub fn encode(
+    mut writer: impl io::Write,
+    frame: &Frame,
+    mut flags: Flags,
+    compression_threshold: Option<usize>,
+    ciphers: &Ciphers,
+) -> crate::Result<usize> {
+    let mut content_buf = Vec::new();
+    frame::content::encode(
+        &mut content_buf,
+        frame.content(),
+        tag::Id3v24,
+        Encoding::UTF8,
+    )?;
+    let encryption_method = if flags.contains(Flags::ENCRYPTION) {
+        frame.encryption_method()
+    } else {
+        None
+    };
+
+    // Frames that could not be decrypted are written as is.
+    let opaque = encryption_method.is_some() && frame.has_encrypted_content();
+    let compress = match compression_threshold {
+        _ if opaque => frame.flags().compression,
+        Some(threshold) => content_buf.len() >= threshold,
+        None => false,
+    };
+    flags.set(Flags::COMPRESSION, compress);
+    // The data length indicator is the size of the frame data before compression and encryption,
+    // which has to be retained for frames that could not be decrypted.
+    let data_length = if opaque {
+        if frame.flags().data_length_indicator {
+            frame.data_length().map(|len| len as usize)
         } else {
-            let mut content_buf = Vec::new();
-            frame::content::encode(
-                &mut content_buf,
-                frame.content(),
-                tag::Id3v24,
-                Encoding::UTF8,
-            )?;
-            (content_buf, 0, None)
-        };
+            None
+        }
+    } else if compress || encryption_method.is_some() || frame.flags().data_length_indicator {
+        Some(content_buf.len())
+    } else {
+        None
+    };
+    flags.set(Flags::DATA_LENGTH_INDICATOR, data_length.is_some());
+    if compress && !opaque {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content_buf)?;
+        content_buf = encoder.finish()?;
+    }
+    let comp_hint_delta = if data_length.is_some() { 4 } else { 0 };
+
+    if let Some(method) = encryption_method {
+        content_buf = super::encrypt_content(frame, method, content_buf, ciphers)?;
+    }
     if flags.contains(Flags::UNSYNCHRONISATION) {
         unsynch::encode_vec(&mut content_buf);
     }
@@ -95,15 +172,25 @@ pub fn encode(mut writer: impl io::Write, frame: &Frame, flags: Flags) -> crate:
         assert_eq!(4, id.len());
         id
     })?;
+    let group_id = if flags.contains(Flags::GROUPING_IDENTITY) {
+        frame.group_id()
+    } else {
+        None
+    };
+    let extra_delta = group_id.iter().chain(encryption_method.iter()).count();
     writer.write_u32::<BigEndian>(unsynch::encode_u32(
-        (content_buf.len() + comp_hint_delta) as u32,
+        (content_buf.len() + comp_hint_delta + extra_delta) as u32,
     ))?;
     writer.write_u16::<BigEndian>(flags.bits())?;
-    if let Some(s) = decompressed_size {
-        if flags.contains(Flags::DATA_LENGTH_INDICATOR) {
-            writer.write_u32::<BigEndian>(unsynch::encode_u32(s as u32))?;
-        }
+    if let Some(group_id) = group_id {
+        writer.write_u8(group_id)?;
+    }
+    if let Some(method) = encryption_method {
+        writer.write_u8(method)?;
+    }
+    if let Some(s) = data_length {
+        writer.write_u32::<BigEndian>(unsynch::encode_u32(s as u32))?;
     }
     writer.write_all(&content_buf)?;
-    Ok(10 + comp_hint_delta + content_buf.len())
+    Ok(10 + comp_hint_delta + extra_delta + content_buf.len())
 }
@@ -1,4 +1,5 @@
 pub mod encoding;
 pub mod frame;
+pub mod restrictions;
 pub mod tag;
 pub mod unsynch;
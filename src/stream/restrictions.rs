@@ -0,0 +1,313 @@
+use crate::extended_header::{
+    ImageEncodingRestriction, ImageSizeRestriction, RestrictionPolicy, Restrictions,
+};
+use crate::frame::{Content, Frame};
+use crate::{Error, ErrorKind};
+use byteorder::{BigEndian, ByteOrder};
+
+/// Applies the text and image restrictions to a frame.
+///
+/// Returns the frame as it should be encoded or None if the frame should be left out of the tag.
+/// If the policy is `RestrictionPolicy::Fail`, an error is returned instead of adapting the frame.
+pub fn restrict_frame(
+    frame: &Frame,
+    restrictions: &Restrictions,
+    policy: RestrictionPolicy,
+) -> crate::Result<Option<Frame>> {
+    if let Content::Picture(ref picture) = *frame.content() {
+        if let Err(err) = check_image(&picture.mime_type, &picture.data, restrictions) {
+            return match policy {
+                RestrictionPolicy::Fail => Err(err),
+                RestrictionPolicy::Adapt => Ok(None),
+            };
+        }
+    }
+
+    let mut restricted = frame.clone();
+    if let Content::Commercial(ref mut commercial) = *restricted.content_mut() {
+        if let Some(ref logo) = commercial.seller_logo {
+            if let Err(err) = check_image(&logo.mime_type, &logo.data, restrictions) {
+                match policy {
+                    RestrictionPolicy::Fail => return Err(err),
+                    // The logo is optional, so only the logo is left out.
+                    RestrictionPolicy::Adapt => commercial.seller_logo = None,
+                }
+            }
+        }
+    }
+
+    let max_chars = match restrictions.text_size.max_chars() {
+        Some(max_chars) => max_chars,
+        None => return Ok(Some(restricted)),
+    };
+    match *restricted.content_mut() {
+        Content::Text(ref mut text) => restrict_values(text, max_chars, policy)?,
+        Content::ExtendedText(ref mut ext) => {
+            restrict_str(&mut ext.description, max_chars, policy)?;
+            restrict_values(&mut ext.value, max_chars, policy)?;
+        }
+        Content::ExtendedLink(ref mut ext) => {
+            restrict_str(&mut ext.description, max_chars, policy)?;
+        }
+        Content::Comment(ref mut comment) => {
+            restrict_str(&mut comment.description, max_chars, policy)?;
+            restrict_str(&mut comment.text, max_chars, policy)?;
+        }
+        Content::Lyrics(ref mut lyrics) => {
+            restrict_str(&mut lyrics.description, max_chars, policy)?;
+            restrict_str(&mut lyrics.text, max_chars, policy)?;
+        }
+        Content::SynchronisedLyrics(ref mut lyrics) => {
+            for (_, text) in lyrics.content.iter_mut() {
+                restrict_str(text, max_chars, policy)?;
+            }
+        }
+        Content::Picture(ref mut picture) => {
+            restrict_str(&mut picture.description, max_chars, policy)?;
+        }
+        Content::EncapsulatedObject(ref mut object) => {
+            restrict_str(&mut object.filename, max_chars, policy)?;
+            restrict_str(&mut object.description, max_chars, policy)?;
+        }
+        Content::TermsOfUse(ref mut terms) => {
+            restrict_str(&mut terms.text, max_chars, policy)?;
+        }
+        Content::Ownership(ref mut ownership) => {
+            restrict_str(&mut ownership.seller, max_chars, policy)?;
+        }
+        Content::Commercial(ref mut commercial) => {
+            restrict_str(&mut commercial.seller, max_chars, policy)?;
+            restrict_str(&mut commercial.description, max_chars, policy)?;
+        }
+        _ => (),
+    }
+    Ok(Some(restricted))
+}
+
+/// Truncates the string to the maximum number of characters or fails, depending on the policy.
+fn restrict_str(s: &mut String, max_chars: usize, policy: RestrictionPolicy) -> crate::Result<()> {
+    if let Some((i, _)) = s.char_indices().nth(max_chars) {
+        if policy == RestrictionPolicy::Fail {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "a string exceeds the text size restriction",
+            ));
+        }
+        s.truncate(i);
+    }
+    Ok(())
+}
+
+/// Applies the text size restriction to each of the null separated values of a text frame.
+fn restrict_values(
+    s: &mut String,
+    max_chars: usize,
+    policy: RestrictionPolicy,
+) -> crate::Result<()> {
+    let mut values = s.split('\0').map(str::to_string).collect::<Vec<_>>();
+    for value in values.iter_mut() {
+        restrict_str(value, max_chars, policy)?;
+    }
+    *s = values.join("\0");
+    Ok(())
+}
+
+/// Checks the image of a picture or a seller logo against the image restrictions.
+fn check_image(mime_type: &str, data: &[u8], restrictions: &Restrictions) -> crate::Result<()> {
+    if restrictions.image_encoding == ImageEncodingRestriction::PNGOrJPEG {
+        let mime_type = mime_type.to_lowercase();
+        if mime_type != "image/png" && mime_type != "image/jpeg" {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "an image is not a PNG or JPEG image as required by the image encoding restriction",
+            ));
+        }
+    }
+    if restrictions.image_size != ImageSizeRestriction::None {
+        let fits = match image_dimensions(data) {
+            Some((width, height)) => match restrictions.image_size {
+                ImageSizeRestriction::None => true,
+                ImageSizeRestriction::Max256x256 => width <= 256 && height <= 256,
+                ImageSizeRestriction::Max64x64 => width <= 64 && height <= 64,
+                ImageSizeRestriction::Exact64x64 => width == 64 && height == 64,
+            },
+            None => false,
+        };
+        if !fits {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "an image does not have dimensions permitted by the image size restriction",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the width and height of a PNG or JPEG image or None if the dimensions could not be
+/// determined.
+fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        // The IHDR chunk is always the first chunk.
+        if data.len() < 24 || &data[12..16] != b"IHDR" {
+            return None;
+        }
+        return Some((
+            BigEndian::read_u32(&data[16..20]),
+            BigEndian::read_u32(&data[20..24]),
+        ));
+    }
+    if !data.starts_with(&[0xff, 0xd8]) {
+        return None;
+    }
+    // Walk the JPEG segments until a start of frame segment is found.
+    let mut i = 2;
+    while i + 4 <= data.len() {
+        if data[i] != 0xff {
+            return None;
+        }
+        let marker = data[i + 1];
+        match marker {
+            0xff => {
+                // Fill byte.
+                i += 1;
+                continue;
+            }
+            0x01 | 0xd0..=0xd7 => {
+                // Markers without a payload.
+                i += 2;
+                continue;
+            }
+            _ => (),
+        }
+        let len = BigEndian::read_u16(&data[i + 2..i + 4]) as usize;
+        let is_sof = (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker);
+        if is_sof {
+            if i + 9 > data.len() {
+                return None;
+            }
+            let height = BigEndian::read_u16(&data[i + 5..i + 7]);
+            let width = BigEndian::read_u16(&data[i + 7..i + 9]);
+            return Some((u32::from(width), u32::from(height)));
+        }
+        i += 2 + len;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extended_header::TextSizeRestriction;
+    use std::fs;
+    use std::io::Read;
+
+    #[test]
+    fn jpeg_dimensions() {
+        let mut data = Vec::new();
+        fs::File::open("testdata/image.jpg")
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        assert_eq!(Some((256, 255)), image_dimensions(&data));
+        assert_eq!(None, image_dimensions(&data[..64]));
+    }
+
+    #[test]
+    fn restrict_text_values() {
+        let restrictions = Restrictions {
+            text_size: TextSizeRestriction::Chars30,
+            ..Restrictions::default()
+        };
+        // The restriction applies to each value, not to all values together.
+        let frame = Frame::with_content(
+            "TPE1",
+            Content::Text("First artist\0Second artist\0Third artist".to_string()),
+        );
+        let restricted = restrict_frame(&frame, &restrictions, RestrictionPolicy::Fail)
+            .unwrap()
+            .unwrap();
+        assert_eq!(frame.content(), restricted.content());
+
+        let frame = Frame::with_content(
+            "TPE1",
+            Content::Text("Artist\0An artist that has a rather long name".to_string()),
+        );
+        assert!(restrict_frame(&frame, &restrictions, RestrictionPolicy::Fail).is_err());
+        let restricted = restrict_frame(&frame, &restrictions, RestrictionPolicy::Adapt)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Some("Artist\0An artist that has a rather lo"),
+            restricted.content().text()
+        );
+    }
+
+    #[test]
+    fn restrict_commercial() {
+        use crate::extended_header::ImageSizeRestriction;
+        use crate::frame::{Commercial, ReceivedAs, SellerLogo};
+
+        let restrictions = Restrictions {
+            text_size: TextSizeRestriction::Chars30,
+            image_size: ImageSizeRestriction::Max64x64,
+            ..Restrictions::default()
+        };
+        let mut logo = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        logo.extend(&[0, 0, 1, 0, 0, 0, 1, 0, 8, 6, 0, 0, 0]);
+        let frame = Frame::with_content(
+            "COMR",
+            Content::Commercial(Commercial {
+                price: "USD9.99".to_string(),
+                valid_until: "20301231".to_string(),
+                contact_url: "https://example.com".to_string(),
+                received_as: ReceivedAs::FileOverInternet,
+                seller: "A seller that has a rather long name".to_string(),
+                description: "Description".to_string(),
+                seller_logo: Some(SellerLogo {
+                    mime_type: "image/png".to_string(),
+                    data: logo,
+                }),
+            }),
+        );
+        assert!(restrict_frame(&frame, &restrictions, RestrictionPolicy::Fail).is_err());
+        let restricted = restrict_frame(&frame, &restrictions, RestrictionPolicy::Adapt)
+            .unwrap()
+            .unwrap();
+        let commercial = restricted.content().commercial().unwrap();
+        assert_eq!("A seller that has a rather lon", commercial.seller);
+        assert_eq!("Description", commercial.description);
+        assert!(commercial.seller_logo.is_none());
+    }
+
+    #[test]
+    fn restrict_terms_of_use() {
+        use crate::frame::TermsOfUse;
+
+        let restrictions = Restrictions {
+            text_size: TextSizeRestriction::Chars30,
+            ..Restrictions::default()
+        };
+        let frame = Frame::with_content(
+            "USER",
+            Content::TermsOfUse(TermsOfUse {
+                lang: "eng".to_string(),
+                text: "These terms of use are longer than thirty characters".to_string(),
+            }),
+        );
+        assert!(restrict_frame(&frame, &restrictions, RestrictionPolicy::Fail).is_err());
+        let restricted = restrict_frame(&frame, &restrictions, RestrictionPolicy::Adapt)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            "These terms of use are longer ",
+            restricted.content().terms_of_use().unwrap().text
+        );
+    }
+
+    #[test]
+    fn png_dimensions() {
+        let mut data = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        data.extend(&[0, 0, 0, 64, 0, 0, 0, 32, 8, 6, 0, 0, 0]);
+        assert_eq!(Some((64, 32)), image_dimensions(&data));
+    }
+}
@@ -1,13 +1,18 @@
+use crate::extended_header::{ExtendedHeader, RestrictionPolicy, Restrictions};
+use crate::frame::{Ciphers, Content, Frame};
 use crate::storage::{self, PlainStorage, Storage};
 use crate::stream::frame;
+use crate::stream::restrictions;
 use crate::stream::unsynch;
 use crate::tag::{Tag, Version};
 use crate::{Error, ErrorKind};
 use bitflags::bitflags;
 use byteorder::{BigEndian, ByteOrder, ReadBytesExt, WriteBytesExt};
+use flate2::Crc;
 use std::cmp;
 use std::fs;
-use std::io::{self, Read, Write};
+use std::io::{self, Read, Seek, Write};
+use std::ops;
 use std::path::Path;
 
 static DEFAULT_FILE_DISCARD: &[&str] = &[
@@ -24,7 +29,28 @@ bitflags! {
     }
 }
 
-pub fn decode(mut reader: impl io::Read) -> crate::Result<Tag> {
+bitflags! {
+    struct ExtendedFlags: u8 {
+        const UPDATE       = 0x40;
+        const CRC          = 0x20;
+        const RESTRICTIONS = 0x10;
+    }
+}
+
+/// The ID3v2.3 extended header flag indicating that CRC data is present.
+const EXTENDED_FLAG_V3_CRC: u16 = 0x8000;
+
+pub fn decode(reader: impl io::Read) -> crate::Result<Tag> {
+    decode_with_ciphers(reader, &Ciphers::new())
+}
+
+/// Decodes a tag, decrypting encrypted frames with the specified ciphers.
+pub fn decode_with_ciphers(reader: impl io::Read, ciphers: &Ciphers) -> crate::Result<Tag> {
+    decode_tag(reader, ciphers, true)
+}
+
+/// Decodes a tag. The tag that a SEEK frame refers to is only merged if `seek` is set.
+fn decode_tag(mut reader: impl io::Read, ciphers: &Ciphers, seek: bool) -> crate::Result<Tag> {
     let mut tag_header = [0; 10];
     let nread = reader.read(&mut tag_header)?;
     if nread < tag_header.len() || &tag_header[0..3] != b"ID3" {
@@ -58,34 +84,21 @@ pub fn decode(mut reader: impl io::Read) -> crate::Result<Tag> {
     }
 
     let mut offset = tag_header.len();
+    let mut tag = Tag::new();
 
-    // TODO: actually use the extended header data.
     if flags.contains(Flags::EXTENDED_HEADER) {
-        let ext_size = unsynch::decode_u32(reader.read_u32::<BigEndian>()?) as usize;
-        // the extended header size includes itself
-        if ext_size < 6 {
-            return Err(Error::new(
-                ErrorKind::Parsing,
-                "Extended header has a minimum size of 6",
-            ));
-        }
+        let (ext_size, ext_header) = decode_extended_header(
+            &mut reader,
+            version,
+            flags.contains(Flags::UNSYNCHRONISATION),
+        )?;
         offset += ext_size;
-        let ext_remaining_size = ext_size - 4;
-        let mut ext_header = Vec::with_capacity(cmp::min(ext_remaining_size, 0xffff));
-        reader
-            .by_ref()
-            .take(ext_remaining_size as u64)
-            .read_to_end(&mut ext_header)?;
-        if flags.contains(Flags::UNSYNCHRONISATION) {
-            unsynch::decode_vec(&mut ext_header);
-        }
+        tag.set_extended_header(ext_header);
     }
 
-    let mut tag = Tag::new();
-
     if version == Version::Id3v22 {
         //limit the reader only to the given tag_size, don't return any more bytes after that.
-        let v2_reader = reader.take(tag_size as u64);
+        let v2_reader = reader.by_ref().take(tag_size as u64);
 
         if flags.contains(Flags::UNSYNCHRONISATION) {
             //unwrap all 'unsynchronized' bytes in the tag before parsing frames
@@ -94,21 +107,402 @@ pub fn decode(mut reader: impl io::Read) -> crate::Result<Tag> {
             decode_v2_frames(v2_reader, &mut tag)?;
         }
     } else {
-        while offset < tag_size + tag_header.len() {
-            let (bytes_read, frame) = match frame::decode(
-                &mut reader,
-                version,
-                flags.contains(Flags::UNSYNCHRONISATION),
-            )? {
+        let unsynchronisation = flags.contains(Flags::UNSYNCHRONISATION);
+        let frames_size = (tag_size + tag_header.len()).saturating_sub(offset);
+        let mut frames_reader = reader.by_ref().take(frames_size as u64);
+        match tag
+            .extended_header()
+            .and_then(|ext_header| ext_header.crc32)
+        {
+            Some(expected_crc) => {
+                // Buffer the frame data, so the CRC can be computed over the exact bytes that
+                // were read.
+                let mut data = Vec::new();
+                frames_reader.read_to_end(&mut data)?;
+                let frames_len = decode_frames(
+                    &data[..],
+                    version,
+                    unsynchronisation,
+                    frames_size,
+                    ciphers,
+                    &mut tag,
+                )?;
+                // The ID3v2.4 CRC includes the padding, the ID3v2.3 CRC is calculated over the
+                // frame data before unsynchronisation and excludes the padding.
+                let mut frame_data = match version {
+                    Version::Id3v23 => data[..cmp::min(frames_len, data.len())].to_vec(),
+                    _ => data,
+                };
+                if version == Version::Id3v23 && unsynchronisation {
+                    unsynch::decode_vec(&mut frame_data);
+                }
+                let actual_crc = crc32(&frame_data);
+                if actual_crc != expected_crc {
+                    return Err(Error::new(
+                        ErrorKind::CrcMismatch(expected_crc, actual_crc),
+                        "the CRC-32 of the frame data does not match the extended header",
+                    ));
+                }
+            }
+            None => {
+                decode_frames(
+                    &mut frames_reader,
+                    version,
+                    unsynchronisation,
+                    frames_size,
+                    ciphers,
+                    &mut tag,
+                )?;
+            }
+        }
+
+        let seek_offset = tag
+            .get("SEEK")
+            .and_then(|frame| frame.content().seek_offset());
+        if let (true, Version::Id3v24, Some(seek_offset)) = (seek, version, seek_offset) {
+            // Skip the padding and the footer, the offset is counted from the end of the tag.
+            io::copy(&mut frames_reader, &mut io::sink())?;
+            let footer_len = if flags.contains(Flags::FOOTER) { 10 } else { 0 };
+            follow_seek(
+                reader,
+                u64::from(seek_offset) + footer_len,
+                ciphers,
+                &mut tag,
+            )?;
+        }
+    }
+
+    Ok(tag)
+}
+
+/// Decodes the tag that a SEEK frame refers to and merges its frames into the tag.
+///
+/// The next tag is searched for from the minimum offset onwards. If it is found, the SEEK frame
+/// is removed from the tag, since the merged tag no longer refers to another tag. The frames of
+/// the next tag only replace existing frames if it is marked as an update.
+fn follow_seek<'a>(
+    mut reader: impl io::Read + 'a,
+    offset: u64,
+    ciphers: &Ciphers,
+    tag: &mut Tag,
+) -> crate::Result<()> {
+    io::copy(&mut reader.by_ref().take(offset), &mut io::sink())?;
+    let data = match find_tag_header(&mut reader)? {
+        Some((_, data)) => data,
+        None => return Ok(()),
+    };
+    // The reader is boxed to end the recursion of the generic decode functions.
+    let next_reader: Box<dyn io::Read + 'a> = Box::new(io::Cursor::new(data).chain(reader));
+    // The header may be a false match in the audio data, in which case the SEEK frame is kept.
+    let next = match decode_with_ciphers(next_reader, ciphers) {
+        Ok(next) => next,
+        Err(_) => return Ok(()),
+    };
+    tag.remove("SEEK");
+    tag.set_seek_target_merged();
+    let is_update = next
+        .extended_header()
+        .map(|ext_header| ext_header.is_update)
+        .unwrap_or(false);
+    for frame in next.frames() {
+        if is_update || tag.frames().all(|f| f != frame) {
+            tag.add_frame(frame.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Reads from the reader until the header of an ID3v2 tag is found.
+///
+/// Returns the number of bytes that were skipped and the data that was read from the start of the
+/// header onwards, or `None` if the end of the reader is reached first.
+fn find_tag_header(mut reader: impl io::Read) -> io::Result<Option<(u64, Vec<u8>)>> {
+    let mut data = Vec::new();
+    let mut skipped = 0;
+    loop {
+        if let Some(i) = data.windows(10).position(is_tag_header) {
+            data.drain(..i);
+            return Ok(Some((skipped + i as u64, data)));
+        }
+        // Keep the last nine bytes, they may be the start of a header.
+        let discard = data.len().saturating_sub(9);
+        data.drain(..discard);
+        skipped += discard as u64;
+        if reader.by_ref().take(4096).read_to_end(&mut data)? == 0 {
+            return Ok(None);
+        }
+    }
+}
+
+/// Returns true if the data starts with a plausible ID3v2 tag header.
+fn is_tag_header(header: &[u8]) -> bool {
+    &header[0..3] == b"ID3"
+        && (2..=4).contains(&header[3])
+        && header[4] != 0xFF
+        && header[6..10].iter().all(|&b| b & 0x80 == 0)
+}
+
+/// Locates the tag that the SEEK frame of the ID3v2.4 tag at the start of the reader refers to.
+///
+/// The returned range includes the header and footer of the tag. `None` is returned if there is
+/// no SEEK frame or if the tag it refers to can not be decoded.
+pub fn locate_seek_target(
+    mut reader: impl io::Read + io::Seek,
+) -> crate::Result<Option<ops::Range<u64>>> {
+    reader.seek(io::SeekFrom::Start(0))?;
+    let mut tag_header = [0; 10];
+    let nread = reader.read(&mut tag_header)?;
+    if nread < tag_header.len() || !is_tag_header(&tag_header) || tag_header[3] != 4 {
+        return Ok(None);
+    }
+    reader.seek(io::SeekFrom::Start(0))?;
+    let seek_offset = match decode_tag(&mut reader, &Ciphers::new(), false) {
+        Ok(tag) => tag
+            .get("SEEK")
+            .and_then(|frame| frame.content().seek_offset()),
+        Err(_) => None,
+    };
+    let seek_offset = match seek_offset {
+        Some(seek_offset) => u64::from(seek_offset),
+        None => return Ok(None),
+    };
+
+    let search_start = tag_end(&tag_header) + seek_offset;
+    reader.seek(io::SeekFrom::Start(search_start))?;
+    let (skipped, data) = match find_tag_header(&mut reader)? {
+        Some(found) => found,
+        None => return Ok(None),
+    };
+    let start = search_start + skipped;
+    let end = start + tag_end(&data[..10]);
+    if decode(io::Cursor::new(data).chain(&mut reader)).is_err() {
+        return Ok(None);
+    }
+    Ok(Some(start..end))
+}
+
+/// Returns the size of a tag including its header and footer.
+fn tag_end(tag_header: &[u8]) -> u64 {
+    let tag_size = unsynch::decode_u32(BigEndian::read_u32(&tag_header[6..10]));
+    let has_footer =
+        tag_header[3] == 4 && Flags::from_bits_truncate(tag_header[5]).contains(Flags::FOOTER);
+    let footer_len = if has_footer { 10 } else { 0 };
+    10 + u64::from(tag_size) + footer_len
+}
+
+/// Removes the tag that the SEEK frame of the tag at the start of the file refers to.
+///
+/// Its frames are merged into the first tag when reading, so it has to be removed when a tag that
+/// was read that way replaces the first tag.
+pub fn remove_seek_target(file: &mut fs::File) -> crate::Result<()> {
+    if let Some(location) = locate_seek_target(&mut *file)? {
+        let mut storage = PlainStorage::with_padding(&mut *file, location, 0, Some(0));
+        storage.writer()?.flush()?;
+    }
+    file.seek(io::SeekFrom::Start(0))?;
+    Ok(())
+}
+
+/// Decodes ID3v2.3 or ID3v2.4 frames until either `size` bytes have been read or padding is
+/// encountered.
+///
+/// Returns the number of bytes occupied by the frames.
+fn decode_frames(
+    mut reader: impl io::Read,
+    version: Version,
+    unsynchronisation: bool,
+    size: usize,
+    ciphers: &Ciphers,
+    tag: &mut Tag,
+) -> crate::Result<usize> {
+    let mut offset = 0;
+    while offset < size {
+        let (bytes_read, frame) =
+            match frame::decode(&mut reader, version, unsynchronisation, ciphers)? {
                 Some(frame) => frame,
                 None => break, // Padding.
             };
-            tag.add_frame(frame);
-            offset += bytes_read;
+        tag.add_frame(frame);
+        offset += bytes_read;
+    }
+    Ok(offset)
+}
+
+/// Decodes the extended header of an ID3v2.3 or ID3v2.4 tag.
+///
+/// Returns the total size of the extended header in bytes along with the decoded header.
+fn decode_extended_header(
+    mut reader: impl io::Read,
+    version: Version,
+    unsynchronisation: bool,
+) -> crate::Result<(usize, ExtendedHeader)> {
+    // The ID3v2.3 extended header size excludes the size field itself, the ID3v2.4 size includes
+    // it and is stored as a synchsafe integer. Both have a minimum of 6.
+    let size = match version {
+        Version::Id3v23 => reader.read_u32::<BigEndian>()?,
+        _ => unsynch::decode_u32(reader.read_u32::<BigEndian>()?),
+    } as usize;
+    if size < 6 {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "Extended header has a minimum size of 6",
+        ));
+    }
+    let (ext_size, data_size) = match version {
+        Version::Id3v23 => (size + 4, size),
+        _ => (size, size - 4),
+    };
+    let mut data = Vec::with_capacity(cmp::min(data_size, 0xffff));
+    reader
+        .by_ref()
+        .take(data_size as u64)
+        .read_to_end(&mut data)?;
+    if data.len() < data_size {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "insufficient data to decode extended header",
+        ));
+    }
+    if version == Version::Id3v23 && unsynchronisation {
+        unsynch::decode_vec(&mut data);
+    }
+
+    let mut header = ExtendedHeader::default();
+    if version == Version::Id3v23 {
+        let ext_flags = BigEndian::read_u16(&data[0..2]);
+        if ext_flags & EXTENDED_FLAG_V3_CRC != 0 {
+            if data.len() < 10 {
+                return Err(Error::new(
+                    ErrorKind::Parsing,
+                    "insufficient data to decode extended header CRC",
+                ));
+            }
+            header.crc32 = Some(BigEndian::read_u32(&data[6..10]));
         }
+        return Ok((ext_size, header));
     }
 
-    Ok(tag)
+    // Each flag that is set is followed by its data, prefixed with the length of the data.
+    fn split_flag_data(data: &[u8]) -> crate::Result<(&[u8], &[u8])> {
+        let len = match data.first() {
+            Some(len) if data.len() > *len as usize => *len as usize,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Parsing,
+                    "insufficient data to decode extended header flag data",
+                ))
+            }
+        };
+        Ok((&data[1..=len], &data[len + 1..]))
+    }
+    let num_flag_bytes = data[0] as usize;
+    if num_flag_bytes == 0 || data.len() < 1 + num_flag_bytes {
+        return Err(Error::new(
+            ErrorKind::Parsing,
+            "invalid number of extended header flag bytes",
+        ));
+    }
+    let ext_flags = ExtendedFlags::from_bits_truncate(data[1]);
+    let mut next = &data[1 + num_flag_bytes..];
+    if ext_flags.contains(ExtendedFlags::UPDATE) {
+        let (_, n) = split_flag_data(next)?;
+        header.is_update = true;
+        next = n;
+    }
+    if ext_flags.contains(ExtendedFlags::CRC) {
+        let (crc, n) = split_flag_data(next)?;
+        if crc.len() != 5 {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "extended header CRC has an invalid size",
+            ));
+        }
+        // The CRC is stored as a 35 bit synchsafe integer.
+        let crc = crc
+            .iter()
+            .fold(0u64, |acc, b| acc << 7 | u64::from(b & 0x7f));
+        header.crc32 = Some(crc as u32);
+        next = n;
+    }
+    if ext_flags.contains(ExtendedFlags::RESTRICTIONS) {
+        let (restrictions, _) = split_flag_data(next)?;
+        if restrictions.len() != 1 {
+            return Err(Error::new(
+                ErrorKind::Parsing,
+                "extended header restrictions have an invalid size",
+            ));
+        }
+        header.restrictions = Some(Restrictions::from(restrictions[0]));
+    }
+    Ok((ext_size, header))
+}
+
+/// Encodes the extended header of an ID3v2.3 or ID3v2.4 tag.
+///
+/// The CRC is only written if `crc32` is set.
+fn encode_extended_header(
+    header: &ExtendedHeader,
+    version: Version,
+    unsynchronisation: bool,
+    crc32: Option<u32>,
+) -> crate::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    match version {
+        Version::Id3v22 => unreachable!(),
+        Version::Id3v23 => {
+            let mut data = Vec::new();
+            data.write_u16::<BigEndian>(if crc32.is_some() {
+                EXTENDED_FLAG_V3_CRC
+            } else {
+                0
+            })?;
+            // The size of the padding. Padding is managed by the storage instead.
+            data.write_u32::<BigEndian>(0)?;
+            if let Some(crc) = crc32 {
+                data.write_u32::<BigEndian>(crc)?;
+            }
+            // In ID3v2.3, the extended header is unsynchronised along with the frames.
+            if unsynchronisation {
+                unsynch::encode_vec(&mut data);
+            }
+            buf.write_u32::<BigEndian>(data.len() as u32)?;
+            buf.extend(data);
+        }
+        Version::Id3v24 => {
+            let mut ext_flags = ExtendedFlags::empty();
+            let mut flag_data = Vec::new();
+            if header.is_update {
+                ext_flags.insert(ExtendedFlags::UPDATE);
+                flag_data.push(0);
+            }
+            if let Some(crc) = crc32 {
+                ext_flags.insert(ExtendedFlags::CRC);
+                flag_data.push(5);
+                flag_data.extend(
+                    (0..5)
+                        .rev()
+                        .map(|i| (u64::from(crc) >> (7 * i) & 0x7f) as u8),
+                );
+            }
+            if let Some(restrictions) = header.restrictions {
+                ext_flags.insert(ExtendedFlags::RESTRICTIONS);
+                flag_data.push(1);
+                flag_data.push(restrictions.into());
+            }
+            buf.write_u32::<BigEndian>(unsynch::encode_u32(6 + flag_data.len() as u32))?;
+            buf.write_u8(1)?;
+            buf.write_u8(ext_flags.bits())?;
+            buf.extend(flag_data);
+        }
+    }
+    Ok(buf)
+}
+
+/// Computes the CRC-32 as used in the extended header.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc::new();
+    crc.update(data);
+    crc.sum()
 }
 
 pub fn decode_v2_frames(mut reader: impl io::Read, tag: &mut Tag) -> crate::Result<()> {
@@ -135,8 +529,19 @@ pub struct Encoder {
     #[builder(default = "false")]
     unsynchronisation: bool,
     /// Enable compression.
+    ///
+    /// For ID3v2.3 and ID3v2.4, frames are compressed individually according to
+    /// `compression_threshold` and `compression_frames`.
     #[builder(default = "false")]
     compression: bool,
+    /// Only compress frames whose content is at least this many bytes large. Small frames tend to
+    /// grow when compressed.
+    #[builder(default = "0")]
+    compression_threshold: usize,
+    /// The IDs of the frames that should be compressed, e.g. `["APIC", "GEOB", "USLT"]`. All frames
+    /// are compressed if this is empty.
+    #[builder(default)]
+    compression_frames: Vec<String>,
     /// Informs the encoder that the file this tag belongs to has been changed.
     ///
     /// This subsequently discards any tags that have their File Alter Preservation bits set and
@@ -145,6 +550,47 @@ pub struct Encoder {
     ///   AENC, ETCO, EQUA, MLLT, POSS, SYLT, SYTC, RVAD, TENC, TLEN, TSIZ
     #[builder(default = "false")]
     file_altered: bool,
+    /// Write an extended header.
+    ///
+    /// The extended header of the tag is used if it has one, otherwise an empty extended header
+    /// is written. If the extended header has its `crc32` field set, the CRC-32 of the frame data
+    /// is computed and written.
+    ///
+    /// ID3v2.2 tags do not have an extended header, this setting is ignored for that version.
+    #[builder(default = "false")]
+    extended_header: bool,
+    /// Write the CRC-32 of the frame data to the extended header. This allows readers to detect
+    /// corruption of the tag.
+    ///
+    /// Enabling this implies `extended_header`.
+    ///
+    /// ID3v2.2 tags do not have an extended header, this setting is ignored for that version.
+    #[builder(default = "false")]
+    crc: bool,
+    /// The restrictions to encode the tag with. The restrictions are written to the extended
+    /// header, which implies `extended_header`.
+    ///
+    /// Text is always encoded as UTF-8, so the text encoding restriction is always met.
+    ///
+    /// If no restrictions are set and the extended header is written, the restrictions in the
+    /// extended header of the tag are enforced instead.
+    ///
+    /// Restrictions only exist in ID3v2.4, this setting is ignored for other versions.
+    #[builder(default, setter(into))]
+    restrictions: Option<Restrictions>,
+    /// What to do with tags that violate the restrictions.
+    #[builder(default = "RestrictionPolicy::Fail")]
+    restriction_policy: RestrictionPolicy,
+    /// Write the tag in appended form, that is with a footer so it can be located from the end of
+    /// a file. `Encoder::encode_to_path` writes the tag to the end of the file, before any ID3v1
+    /// tag, replacing a tag that was appended earlier.
+    ///
+    /// Footers only exist in ID3v2.4, this setting is ignored for other versions.
+    #[builder(default = "false")]
+    appended: bool,
+    /// The ciphers to encrypt frames with that have their encryption method set.
+    #[builder(default)]
+    ciphers: Ciphers,
 }
 
 impl Encoder {
@@ -164,33 +610,180 @@ impl Encoder {
             .filter(|frame| !self.file_altered || !frame.file_alter_preservation())
             // Check whether this frame is part of the set of frames that should always be
             // discarded when the file is changed.
-            .filter(|frame| !self.file_altered || !DEFAULT_FILE_DISCARD.contains(&frame.id()));
+            .filter(|frame| !self.file_altered || !DEFAULT_FILE_DISCARD.contains(&frame.id()))
+            // Versions before ID3v2.4 have no RVA2 frame, only the one with the identification
+            // "track" can be converted to RVAD.
+            .filter(|frame| match frame.content() {
+                Content::RelativeVolumeAdjustment(rva2) if self.version != Version::Id3v24 => {
+                    rva2.identification.eq_ignore_ascii_case("track")
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>();
 
         let mut flags = Flags::empty();
         flags.set(Flags::UNSYNCHRONISATION, self.unsynchronisation);
         if self.version == Version::Id3v22 {
             flags.set(Flags::COMPRESSION, self.compression);
         }
+        let footer = self.appended && self.version == Version::Id3v24;
+        flags.set(Flags::FOOTER, footer);
+
+        let write_ext_header = (self.extended_header || self.crc || self.restrictions.is_some())
+            && self.version != Version::Id3v22;
+        let mut ext_header = tag.extended_header().cloned().unwrap_or_default();
+        // Restrictions that are written to the extended header are always enforced.
+        let restrictions = match self.version {
+            Version::Id3v24 if write_ext_header => self.restrictions.or(ext_header.restrictions),
+            _ => None,
+        };
+        ext_header.restrictions = restrictions;
+        let write_crc = self.crc || ext_header.crc32.is_some();
 
         let mut frame_data = Vec::new();
-        for frame in saved_frames {
-            frame::encode(&mut frame_data, frame, self.version, self.unsynchronisation)?;
+        match restrictions {
+            Some(restrictions) => {
+                // The size of the extended header does not depend on the value of the CRC.
+                let ext_header_size = encode_extended_header(
+                    &ext_header,
+                    self.version,
+                    self.unsynchronisation,
+                    if write_crc { Some(0) } else { None },
+                )?
+                .len();
+                let footer_size = if footer { 10 } else { 0 };
+                let max_frames = restrictions.tag_size.max_frames();
+                let max_size = restrictions.tag_size.max_size();
+                let mut num_frames = 0;
+                for frame in &saved_frames {
+                    let frame = match restrictions::restrict_frame(
+                        frame,
+                        &restrictions,
+                        self.restriction_policy,
+                    )? {
+                        Some(frame) => frame,
+                        None => continue,
+                    };
+                    let mut buf = Vec::new();
+                    frame::encode(
+                        &mut buf,
+                        &frame,
+                        self.version,
+                        self.unsynchronisation,
+                        self.compression_threshold(&frame),
+                        &self.ciphers,
+                    )?;
+                    if num_frames >= max_frames
+                        || 10 + ext_header_size + frame_data.len() + buf.len() + footer_size
+                            > max_size
+                    {
+                        match self.restriction_policy {
+                            RestrictionPolicy::Fail => {
+                                return Err(Error::new(
+                                    ErrorKind::InvalidInput,
+                                    "the tag exceeds the tag size restriction",
+                                ));
+                            }
+                            RestrictionPolicy::Adapt => continue,
+                        }
+                    }
+                    frame_data.extend(buf);
+                    num_frames += 1;
+                }
+            }
+            None => {
+                for frame in &saved_frames {
+                    frame::encode(
+                        &mut frame_data,
+                        frame,
+                        self.version,
+                        self.unsynchronisation,
+                        self.compression_threshold(frame),
+                        &self.ciphers,
+                    )?;
+                }
+            }
         }
         //in v2, Unsynchronization is applied to the whole tag data at once, not for each frame separately
         if self.version == Version::Id3v22 && self.unsynchronisation {
             unsynch::encode_vec(&mut frame_data)
         }
+
+        let mut ext_header_data = Vec::new();
+        if write_ext_header {
+            let crc = if !write_crc {
+                None
+            } else if self.version == Version::Id3v23 && self.unsynchronisation {
+                // The ID3v2.3 CRC is calculated over the frame data before unsynchronisation.
+                let mut plain_frame_data = frame_data.clone();
+                unsynch::decode_vec(&mut plain_frame_data);
+                Some(crc32(&plain_frame_data))
+            } else {
+                // No padding is written as part of the tag, so the frame data is everything the
+                // ID3v2.4 CRC has to cover.
+                Some(crc32(&frame_data))
+            };
+            ext_header_data =
+                encode_extended_header(&ext_header, self.version, self.unsynchronisation, crc)?;
+            flags.insert(Flags::EXTENDED_HEADER);
+        }
+
+        let size = unsynch::encode_u32((ext_header_data.len() + frame_data.len()) as u32);
         writer.write_all(b"ID3")?;
         writer.write_all(&[self.version.minor() as u8, 0])?;
         writer.write_u8(flags.bits())?;
-        writer.write_u32::<BigEndian>(unsynch::encode_u32(frame_data.len() as u32))?;
+        writer.write_u32::<BigEndian>(size)?;
+        writer.write_all(&ext_header_data[..])?;
         writer.write_all(&frame_data[..])?;
+        if footer {
+            // The footer is a copy of the header with a different identifier.
+            writer.write_all(b"3DI")?;
+            writer.write_all(&[self.version.minor(), 0])?;
+            writer.write_u8(flags.bits())?;
+            writer.write_u32::<BigEndian>(size)?;
+        }
         Ok(())
     }
 
+    /// Returns the size from which the frame should be compressed or None if the frame should not
+    /// be compressed at all.
+    fn compression_threshold(&self, frame: &Frame) -> Option<usize> {
+        let compress = self.compression
+            && (self.compression_frames.is_empty()
+                || self.compression_frames.iter().any(|id| id == frame.id()));
+        if compress {
+            Some(self.compression_threshold)
+        } else {
+            None
+        }
+    }
+
     /// Encodes a tag and replaces any existing tag in the file pointed to by the specified path.
+    ///
+    /// If the tag is encoded in appended form, the tag appended to the end of the file is replaced
+    /// and any tag at the start of the file is left as is. Otherwise, if the frames of a tag that a
+    /// SEEK frame referred to were merged into the tag when it was read, that tag is removed from
+    /// the file as well.
     pub fn encode_to_path(&self, tag: &Tag, path: impl AsRef<Path>) -> crate::Result<()> {
         let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+        if self.appended && self.version == Version::Id3v24 {
+            let location = match storage::locate_id3v2_appended(&mut file)? {
+                Some(location) => location,
+                None => {
+                    let end = storage::locate_id3v1(&mut file)?;
+                    end..end
+                }
+            };
+            // Footers and padding are mutually exclusive.
+            let mut storage = PlainStorage::with_padding(file, location, 0, Some(0));
+            let mut w = storage.writer()?;
+            self.encode(tag, &mut w)?;
+            w.flush()?;
+            return Ok(());
+        }
+        if tag.seek_target_merged() {
+            remove_seek_target(&mut file)?;
+        }
         let location = storage::locate_id3v2(&mut file)?.unwrap_or(0..0); // Create a new tag if none could be located.
 
         let mut storage = PlainStorage::new(file, location);
@@ -234,13 +827,25 @@ mod benchmarks {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::extended_header::{
+        ImageEncodingRestriction, ImageSizeRestriction, TagSizeRestriction,
+        TextEncodingRestriction, TextSizeRestriction,
+    };
     use crate::frame::{
-        Content, Frame, Picture, PictureType, SynchronisedLyrics, SynchronisedLyricsType,
+        Content, Frame, Lyrics, Picture, PictureType, SynchronisedLyrics, SynchronisedLyricsType,
         TimestampFormat,
     };
     use std::fs;
     use std::io;
 
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn encoder_is_send_sync() {
+        assert_send_sync::<Encoder>();
+        assert_send_sync::<Ciphers>();
+    }
+
     fn make_tag() -> Tag {
         let mut tag = Tag::new();
         tag.set_title("Title");
@@ -324,6 +929,10 @@ mod tests {
         assert_eq!("Artist", tag.artist().unwrap());
         assert_eq!("Album", tag.album().unwrap());
         assert_eq!(2, tag.track().unwrap());
+        let ext_header = tag.extended_header().unwrap();
+        assert!(!ext_header.is_update);
+        assert!(ext_header.crc32.is_none());
+        assert_eq!(Some(Restrictions::default()), ext_header.restrictions);
     }
 
     #[test]
@@ -401,6 +1010,53 @@ mod tests {
         assert_eq!(tag, tag_read);
     }
 
+    #[test]
+    fn write_compression_threshold() {
+        let mut tag = make_tag();
+        tag.add_frame(Frame::with_content(
+            "USLT",
+            Content::Lyrics(Lyrics {
+                lang: "eng".to_string(),
+                description: "".to_string(),
+                text: "la ".repeat(4096),
+            }),
+        ));
+        for (version, compression_flags) in &[(Version::Id3v23, 0x0080), (Version::Id3v24, 0x0009)]
+        {
+            let mut uncompressed = Vec::new();
+            EncoderBuilder::default()
+                .version(*version)
+                .build()
+                .unwrap()
+                .encode(&tag, &mut uncompressed)
+                .unwrap();
+            let mut buffer = Vec::new();
+            EncoderBuilder::default()
+                .version(*version)
+                .compression(true)
+                .compression_threshold(1024)
+                .compression_frames(vec!["USLT".to_string(), "TIT2".to_string()])
+                .build()
+                .unwrap()
+                .encode(&tag, &mut buffer)
+                .unwrap();
+            assert!(buffer.len() < uncompressed.len() - 10_000);
+            let frame_flags = |id: &[u8]| {
+                let i = buffer.windows(4).position(|w| w == id).unwrap();
+                BigEndian::read_u16(&buffer[i + 8..i + 10])
+            };
+            assert_eq!(*compression_flags, frame_flags(b"USLT"));
+            // Below the threshold.
+            assert_eq!(0, frame_flags(b"TIT2"));
+            // Not in the list of frames to compress.
+            assert_eq!(0, frame_flags(b"APIC"));
+
+            let tag_read = decode(&mut io::Cursor::new(buffer)).unwrap();
+            assert_eq!(tag, tag_read);
+            assert_eq!("la ".repeat(4096), tag_read.lyrics().next().unwrap().text);
+        }
+    }
+
     #[test]
     fn write_id3v23_unsynch() {
         let tag = make_tag();
@@ -460,6 +1116,437 @@ mod tests {
         assert_eq!(tag, tag_read);
     }
 
+    #[test]
+    fn write_id3v23_extended_header() {
+        let mut tag = make_tag();
+        tag.set_extended_header(ExtendedHeader {
+            is_update: true,
+            crc32: Some(0),
+            restrictions: Some(Restrictions::from(0xff)),
+        });
+        for unsynchronisation in &[false, true] {
+            let mut buffer = Vec::new();
+            EncoderBuilder::default()
+                .version(Version::Id3v23)
+                .unsynchronisation(*unsynchronisation)
+                .extended_header(true)
+                .build()
+                .unwrap()
+                .encode(&tag, &mut buffer)
+                .unwrap();
+            let tag_read = decode(&mut io::Cursor::new(buffer)).unwrap();
+            assert_eq!(tag, tag_read);
+            let ext_header = tag_read.extended_header().unwrap();
+            // Updates and restrictions can not be represented in ID3v2.3.
+            assert!(!ext_header.is_update);
+            assert!(ext_header.crc32.is_some());
+            assert!(ext_header.restrictions.is_none());
+        }
+    }
+
+    #[test]
+    fn write_id3v23_extended_header_unsynch() {
+        // The CRC of this tag contains 0xff 0x00, which has to be unsynchronised.
+        let mut tag = Tag::new();
+        tag.set_title("Title 17215");
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .version(Version::Id3v23)
+            .unsynchronisation(true)
+            .crc(true)
+            .build()
+            .unwrap()
+            .encode(&tag, &mut buffer)
+            .unwrap();
+        assert_eq!(&[0, 0, 0, 11], &buffer[10..14]);
+        assert_eq!(&[0xff, 0, 0, 0xda, 0x24], &buffer[20..25]);
+        let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+        assert_eq!(tag, tag_read);
+        assert_eq!(Some(0xff00_da24), tag_read.extended_header().unwrap().crc32);
+    }
+
+    #[test]
+    fn write_id3v24_extended_header() {
+        let mut tag = make_tag();
+        let ext_header = ExtendedHeader {
+            is_update: true,
+            crc32: None,
+            restrictions: Some(Restrictions::from(0b1011_0101)),
+        };
+        tag.set_extended_header(ext_header.clone());
+        let mut buffer = Vec::new();
+        tag.write_to(&mut buffer, Version::Id3v24).unwrap();
+        let tag_read = decode(&mut io::Cursor::new(buffer)).unwrap();
+        assert_eq!(tag, tag_read);
+        assert_eq!(Some(&ext_header), tag_read.extended_header());
+
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .version(Version::Id3v24)
+            .build()
+            .unwrap()
+            .encode(&tag, &mut buffer)
+            .unwrap();
+        let tag_read = decode(&mut io::Cursor::new(buffer)).unwrap();
+        assert_eq!(tag, tag_read);
+        assert!(tag_read.extended_header().is_none());
+    }
+
+    #[test]
+    fn write_crc() {
+        let tag = make_tag();
+        for (version, unsynchronisation) in &[
+            (Version::Id3v23, false),
+            (Version::Id3v23, true),
+            (Version::Id3v24, false),
+            (Version::Id3v24, true),
+        ] {
+            let mut buffer = Vec::new();
+            EncoderBuilder::default()
+                .version(*version)
+                .unsynchronisation(*unsynchronisation)
+                .crc(true)
+                .build()
+                .unwrap()
+                .encode(&tag, &mut buffer)
+                .unwrap();
+            let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+            assert_eq!(tag, tag_read);
+            assert!(tag_read.extended_header().unwrap().crc32.is_some());
+
+            // Corrupt the image data.
+            let i = buffer.windows(4).position(|w| w == b"JFIF").unwrap();
+            buffer[i] = b'j';
+            match decode(&mut io::Cursor::new(&buffer)) {
+                Err(Error {
+                    kind: ErrorKind::CrcMismatch(expected, actual),
+                    ..
+                }) => assert_ne!(expected, actual),
+                rs => panic!("expected a CRC mismatch, got {:?}", rs),
+            }
+        }
+    }
+
+    #[test]
+    fn read_crc_padding() {
+        let mut frame_data = Vec::new();
+        for version in &[Version::Id3v23, Version::Id3v24] {
+            frame_data.clear();
+            frame::encode(
+                &mut frame_data,
+                &Frame::with_content("TIT2", Content::Text("Title".to_string())),
+                *version,
+                false,
+                None,
+                &Ciphers::new(),
+            )
+            .unwrap();
+            let padded_len = frame_data.len() + 16;
+            let crc = match version {
+                // The ID3v2.3 CRC excludes the padding.
+                Version::Id3v23 => crc32(&frame_data),
+                // The ID3v2.4 CRC includes the padding.
+                _ => {
+                    let mut padded = frame_data.clone();
+                    padded.resize(padded_len, 0);
+                    crc32(&padded)
+                }
+            };
+            let ext_header =
+                encode_extended_header(&ExtendedHeader::default(), *version, false, Some(crc))
+                    .unwrap();
+            let mut buffer = Vec::new();
+            buffer.extend(b"ID3");
+            buffer.extend(&[version.minor(), 0, Flags::EXTENDED_HEADER.bits()]);
+            buffer
+                .write_u32::<BigEndian>(unsynch::encode_u32((ext_header.len() + padded_len) as u32))
+                .unwrap();
+            buffer.extend(&ext_header);
+            buffer.extend(&frame_data);
+            buffer.resize(buffer.len() + 16, 0);
+            let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+            assert_eq!(Some("Title"), tag_read.title());
+
+            // Corrupt the padding, which is only covered by the ID3v2.4 CRC.
+            let len = buffer.len();
+            buffer[len - 1] = 1;
+            let rs = decode(&mut io::Cursor::new(&buffer));
+            match version {
+                Version::Id3v23 => assert!(rs.is_ok()),
+                _ => match rs {
+                    Err(Error {
+                        kind: ErrorKind::CrcMismatch(..),
+                        ..
+                    }) => (),
+                    rs => panic!("expected a CRC mismatch, got {:?}", rs),
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn write_restrictions() {
+        let mut tag = make_tag();
+        tag.set_album("An album title that is longer than thirty characters");
+        let restrictions = Restrictions {
+            tag_size: TagSizeRestriction::Frames32Size4K,
+            text_encoding: TextEncodingRestriction::Latin1OrUTF8,
+            text_size: TextSizeRestriction::Chars30,
+            image_encoding: ImageEncodingRestriction::PNGOrJPEG,
+            image_size: ImageSizeRestriction::Max256x256,
+        };
+
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .version(Version::Id3v24)
+            .restrictions(restrictions)
+            .restriction_policy(RestrictionPolicy::Adapt)
+            .build()
+            .unwrap()
+            .encode(&tag, &mut buffer)
+            .unwrap();
+        assert!(buffer.len() <= 4096);
+        let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+        assert_eq!(
+            Some(restrictions),
+            tag_read.extended_header().unwrap().restrictions
+        );
+        assert_eq!(Some("An album title that is longer "), tag_read.album());
+        assert_eq!(Some("Title"), tag_read.title());
+        // The picture does not fit in 4KB.
+        assert_eq!(0, tag_read.pictures().count());
+
+        for restrictions in &[
+            restrictions,
+            Restrictions {
+                text_size: TextSizeRestriction::None,
+                ..restrictions
+            },
+        ] {
+            match EncoderBuilder::default()
+                .version(Version::Id3v24)
+                .restrictions(*restrictions)
+                .build()
+                .unwrap()
+                .encode(&tag, &mut Vec::new())
+            {
+                Err(Error {
+                    kind: ErrorKind::InvalidInput,
+                    ..
+                }) => (),
+                rs => panic!("expected the restrictions to be violated, got {:?}", rs),
+            }
+        }
+
+        let mut tag = make_tag();
+        tag.remove_picture_by_type(PictureType::CoverFront);
+        EncoderBuilder::default()
+            .version(Version::Id3v24)
+            .restrictions(restrictions)
+            .build()
+            .unwrap()
+            .encode(&tag, &mut Vec::new())
+            .unwrap();
+
+        // The restrictions in the extended header of the tag are enforced as well.
+        let mut tag = make_tag();
+        tag.set_extended_header(ExtendedHeader {
+            restrictions: Some(restrictions),
+            ..ExtendedHeader::default()
+        });
+        assert!(tag.write_to(&mut Vec::new(), Version::Id3v24).is_err());
+        tag.remove_picture_by_type(PictureType::CoverFront);
+        let mut buffer = Vec::new();
+        tag.write_to(&mut buffer, Version::Id3v24).unwrap();
+        let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+        assert_eq!(
+            Some(restrictions),
+            tag_read.extended_header().unwrap().restrictions
+        );
+    }
+
+    #[test]
+    fn write_id3v24_appended() {
+        let tag = make_tag();
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .version(Version::Id3v24)
+            .appended(true)
+            .build()
+            .unwrap()
+            .encode(&tag, &mut buffer)
+            .unwrap();
+        let footer = &buffer[buffer.len() - 10..];
+        assert_eq!(b"3DI", &footer[..3]);
+        assert_eq!(&buffer[3..10], &footer[3..]);
+        assert_eq!(Flags::FOOTER.bits(), buffer[5]);
+        let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+        assert_eq!(tag, tag_read);
+    }
+
+    #[test]
+    fn write_id3v24_appended_to_path() {
+        let tmp = tempdir::TempDir::new("id3_appended").unwrap();
+        let path = tmp.path().join("file.mp3");
+        let mut audio = vec![0xff; 1024];
+        audio.extend(b"TAG");
+        audio.extend(vec![0; 125]);
+        fs::write(&path, &audio).unwrap();
+
+        let encoder = EncoderBuilder::default()
+            .version(Version::Id3v24)
+            .appended(true)
+            .build()
+            .unwrap();
+        let mut tag = make_tag();
+        encoder.encode_to_path(&tag, &path).unwrap();
+        assert_eq!(tag, Tag::read_from_path(&path).unwrap());
+        let len = fs::metadata(&path).unwrap().len();
+
+        tag.set_title("Another title");
+        encoder.encode_to_path(&tag, &path).unwrap();
+        assert_eq!(tag, Tag::read_from_path(&path).unwrap());
+        assert_eq!(len + 8, fs::metadata(&path).unwrap().len());
+
+        let data = fs::read(&path).unwrap();
+        assert_eq!(&audio[..1024], &data[..1024]);
+        assert_eq!(&audio[1024..], &data[data.len() - 128..]);
+    }
+
+    #[test]
+    fn write_encrypted() {
+        use crate::frame::{EncryptionMethodRegistration, FrameCipher};
+
+        struct Xor(u8);
+        impl FrameCipher for Xor {
+            fn decrypt(&self, _: &str, data: &[u8]) -> crate::Result<Vec<u8>> {
+                Ok(data.iter().map(|b| b ^ self.0).collect())
+            }
+            fn encrypt(&self, frame_id: &str, data: &[u8]) -> crate::Result<Vec<u8>> {
+                self.decrypt(frame_id, data)
+            }
+        }
+        let mut ciphers = Ciphers::new();
+        ciphers.register(0x80, Xor(0xa5));
+
+        let mut tag = make_tag();
+        tag.add_frame(Frame::with_content(
+            "ENCR",
+            Content::EncryptionMethodRegistration(EncryptionMethodRegistration {
+                owner_identifier: "http://example.com".to_string(),
+                method_symbol: 0x80,
+                data: Vec::new(),
+            }),
+        ));
+        let mut frame = Frame::with_content("TALB", Content::Text("Secret album".to_string()));
+        frame.set_encryption_method(Some(0x80));
+        tag.add_frame(frame);
+
+        for version in &[Version::Id3v23, Version::Id3v24] {
+            for unsynchronisation in &[false, true] {
+                let mut buffer = Vec::new();
+                EncoderBuilder::default()
+                    .version(*version)
+                    .unsynchronisation(*unsynchronisation)
+                    .compression(true)
+                    .ciphers(ciphers.clone())
+                    .build()
+                    .unwrap()
+                    .encode(&tag, &mut buffer)
+                    .unwrap();
+                assert!(buffer
+                    .windows("Secret album".len())
+                    .all(|w| w != b"Secret album"));
+                let tag_read =
+                    decode_with_ciphers(&mut io::Cursor::new(&buffer), &ciphers).unwrap();
+                assert_eq!(Some("Secret album"), tag_read.album());
+                assert_eq!(
+                    Some(0x80),
+                    tag_read.get("TALB").unwrap().encryption_method()
+                );
+
+                // Without the cipher, the encrypted frame is kept as is.
+                let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+                let frame = tag_read.get("TALB").unwrap();
+                assert!(frame.content().unknown().is_some());
+                assert!(frame.has_encrypted_content());
+                assert_eq!(Some(0x80), frame.encryption_method());
+                let mut buffer = Vec::new();
+                EncoderBuilder::default()
+                    .version(*version)
+                    .unsynchronisation(*unsynchronisation)
+                    .build()
+                    .unwrap()
+                    .encode(&tag_read, &mut buffer)
+                    .unwrap();
+                let tag_read =
+                    decode_with_ciphers(&mut io::Cursor::new(&buffer), &ciphers).unwrap();
+                assert_eq!(Some("Secret album"), tag_read.album());
+            }
+        }
+
+        // Encrypting requires the cipher.
+        assert!(EncoderBuilder::default()
+            .build()
+            .unwrap()
+            .encode(&tag, &mut Vec::new())
+            .is_err());
+
+        // Only frames that could not be decrypted are written as is, other frames with unknown
+        // content are encrypted as well.
+        let mut tag = Tag::new();
+        let mut frame = Frame::with_content("ZZZZ", Content::Unknown(b"Plain data".to_vec()));
+        frame.set_encryption_method(Some(0x80));
+        tag.add_frame(frame);
+        assert!(EncoderBuilder::default()
+            .build()
+            .unwrap()
+            .encode(&tag, &mut Vec::new())
+            .is_err());
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .ciphers(ciphers.clone())
+            .build()
+            .unwrap()
+            .encode(&tag, &mut buffer)
+            .unwrap();
+        assert!(buffer.windows(10).all(|w| w != b"Plain data"));
+    }
+
+    #[test]
+    fn write_read_tag_alter_preservation() {
+        let mut frame = Frame::with_content("TENC", Content::Text("encoder".to_string()));
+        frame.set_tag_alter_preservation(true);
+        let mut buffer = Vec::new();
+        let mut frame_data = Vec::new();
+        frame::encode(
+            &mut frame_data,
+            &frame,
+            Version::Id3v24,
+            false,
+            None,
+            &Ciphers::new(),
+        )
+        .unwrap();
+        buffer.extend(b"ID3\x04\x00\x00");
+        buffer
+            .write_u32::<BigEndian>(unsynch::encode_u32(frame_data.len() as u32))
+            .unwrap();
+        buffer.extend(frame_data);
+        let tag_read = decode(&mut io::Cursor::new(&buffer)).unwrap();
+        assert!(tag_read.get("TENC").unwrap().tag_alter_preservation());
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .build()
+            .unwrap()
+            .encode(&tag_read, &mut buffer)
+            .unwrap();
+        assert!(decode(&mut io::Cursor::new(&buffer))
+            .unwrap()
+            .get("TENC")
+            .is_none());
+    }
+
     #[test]
     fn write_id3v24_alter_file() {
         let mut tag = Tag::new();
@@ -477,4 +1564,299 @@ mod tests {
         let tag_read = decode(&mut io::Cursor::new(buffer)).unwrap();
         assert!(tag_read.get("TLEN").is_none());
     }
+
+    #[test]
+    fn decode_following_seek() {
+        let mut first = Tag::new();
+        first.set_title("Title");
+        first.add_frame(Frame::with_content("SEEK", Content::SeekOffset(64)));
+        let mut data = Vec::new();
+        first.write_to(&mut data, Version::Id3v24).unwrap();
+
+        // Without another tag, the SEEK frame is retained.
+        let mut audio = data.clone();
+        audio.extend(vec![0xFF; 128]);
+        let tag = decode(&audio[..]).unwrap();
+        assert_eq!(Some(64), tag.get("SEEK").unwrap().content().seek_offset());
+
+        // The next tag starts at least the seek offset after the end of the first tag.
+        data.extend(vec![0xFF; 80]);
+        let mut second = Tag::new();
+        second.set_title("Other title");
+        second.set_album("Album");
+        second.write_to(&mut data, Version::Id3v24).unwrap();
+        data.extend(vec![0xFF; 32]);
+        let tag = decode(&data[..]).unwrap();
+        assert!(tag.get("SEEK").is_none());
+        assert_eq!(Some("Title"), tag.title());
+        assert_eq!(Some("Album"), tag.album());
+
+        // Frames of a tag that is marked as an update replace the existing frames.
+        let mut update = Vec::new();
+        first.write_to(&mut update, Version::Id3v24).unwrap();
+        update.extend(vec![0xFF; 64]);
+        second.set_extended_header(ExtendedHeader {
+            is_update: true,
+            ..ExtendedHeader::default()
+        });
+        second.write_to(&mut update, Version::Id3v24).unwrap();
+        let tag = decode(&update[..]).unwrap();
+        assert_eq!(Some("Other title"), tag.title());
+        assert_eq!(Some("Album"), tag.album());
+
+        // Data that only resembles a header does not fail the read.
+        let mut false_match = Vec::new();
+        first.write_to(&mut false_match, Version::Id3v24).unwrap();
+        false_match.extend(vec![0xFF; 64]);
+        false_match.extend(b"ID3\x04\x00\x00\x00\x00\x00\x7F");
+        false_match.extend(vec![0xFF; 128]);
+        let tag = decode(&false_match[..]).unwrap();
+        assert_eq!(Some(64), tag.get("SEEK").unwrap().content().seek_offset());
+    }
+
+    #[test]
+    fn write_following_seek() {
+        let mut first = Tag::new();
+        first.set_title("Title");
+        first.add_frame(Frame::with_content("SEEK", Content::SeekOffset(16)));
+        let mut data = Vec::new();
+        first.write_to(&mut data, Version::Id3v24).unwrap();
+        let first_len = data.len();
+        data.extend(vec![0xFF; 16]);
+        let mut second = Tag::new();
+        second.set_album("Album");
+        second.write_to(&mut data, Version::Id3v24).unwrap();
+        data.extend(vec![0xFF; 32]);
+
+        let tmp = tempdir::TempDir::new("id3_seek").unwrap();
+        let path = tmp.path().join("file.mp3");
+        fs::write(&path, &data).unwrap();
+        let location = locate_seek_target(fs::File::open(&path).unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(data.len() as u64 - 32, location.end);
+
+        // Writing a new tag leaves the SEEK frame and the tag it refers to as is.
+        let mut tag = Tag::new();
+        tag.set_title("Title");
+        tag.add_frame(Frame::with_content("SEEK", Content::SeekOffset(16)));
+        tag.write_to_path(&path, Version::Id3v24).unwrap();
+        assert!(fs::read(&path).unwrap().ends_with(&data[first_len..]));
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(Some("Album"), tag.album());
+
+        // Writing a tag that was read from the file replaces both tags.
+        let mut tag = Tag::read_from_path(&path).unwrap();
+        tag.set_artist("Artist");
+        tag.write_to_path(&path, Version::Id3v24).unwrap();
+        let data = fs::read(&path).unwrap();
+        assert_eq!(1, data.windows(3).filter(|w| w == b"ID3").count());
+        assert!(data.ends_with(&[0xFF; 48]));
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(1, tag.frames().filter(|f| f.id() == "TALB").count());
+        assert_eq!(Some("Title"), tag.title());
+        assert_eq!(Some("Artist"), tag.artist());
+    }
+
+    #[test]
+    fn write_chapters() {
+        use crate::frame::{Chapter, TableOfContents};
+
+        let mut tag = Tag::new();
+        for (i, start_time) in [0, 60_000, 120_000].iter().enumerate() {
+            tag.add_frame(Frame::with_content(
+                "CHAP",
+                Content::Chapter(Chapter {
+                    element_id: format!("ch{}", i),
+                    start_time: *start_time,
+                    end_time: start_time + 60_000,
+                    start_offset: None,
+                    end_offset: None,
+                    frames: vec![Frame::with_content(
+                        "TIT2",
+                        Content::Text(format!("Chapter {}", i)),
+                    )],
+                }),
+            ));
+        }
+        tag.add_frame(Frame::with_content(
+            "CTOC",
+            Content::TableOfContents(TableOfContents {
+                element_id: "toc".to_string(),
+                top_level: true,
+                ordered: true,
+                elements: vec!["ch2".to_string(), "sub".to_string()],
+                frames: vec![Frame::with_content(
+                    "TIT2",
+                    Content::Text("Contents".to_string()),
+                )],
+            }),
+        ));
+        tag.add_frame(Frame::with_content(
+            "CTOC",
+            Content::TableOfContents(TableOfContents {
+                element_id: "sub".to_string(),
+                top_level: false,
+                ordered: true,
+                elements: vec!["ch0".to_string(), "ch1".to_string()],
+                frames: Vec::new(),
+            }),
+        ));
+        for version in &[Version::Id3v23, Version::Id3v24] {
+            let mut buffer = Vec::new();
+            EncoderBuilder::default()
+                .version(*version)
+                .build()
+                .unwrap()
+                .encode(&tag, &mut buffer)
+                .unwrap();
+            let tag_read = decode(&mut io::Cursor::new(buffer)).unwrap();
+            let chapters = tag_read
+                .chapters()
+                .iter()
+                .map(|chapter| chapter.frames[0].content().text().unwrap().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(vec!["Chapter 2", "Chapter 0", "Chapter 1"], chapters);
+            assert_eq!(
+                Some("Contents"),
+                tag_read.tables_of_contents().next().unwrap().frames[0]
+                    .content()
+                    .text()
+            );
+        }
+
+        // Chapters and tables of contents are unique by their element ID.
+        let sub = TableOfContents {
+            element_id: "sub".to_string(),
+            top_level: false,
+            ordered: false,
+            elements: vec!["ch1".to_string(), "ch0".to_string()],
+            frames: Vec::new(),
+        };
+        assert_ne!(
+            tag.tables_of_contents().find(|toc| toc.element_id == "sub"),
+            Some(&sub)
+        );
+        tag.add_frame(Frame::with_content("CTOC", Content::TableOfContents(sub)));
+        assert_eq!(2, tag.tables_of_contents().count());
+        let mut chapter = tag.chapters()[1].clone();
+        assert_eq!("ch0", chapter.element_id);
+        chapter.start_time = 180_000;
+        chapter.end_time = 240_000;
+        tag.add_frame(Frame::with_content("CHAP", Content::Chapter(chapter)));
+        assert_eq!(3, tag.frames().filter(|f| f.id() == "CHAP").count());
+        // The elements of a table of contents that is not ordered are sorted by their start time.
+        let chapters = tag
+            .chapters()
+            .iter()
+            .map(|chapter| chapter.element_id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["ch2", "ch1", "ch0"], chapters);
+    }
+
+    #[test]
+    fn write_volume_adjustment_conversion() {
+        let mut tag = Tag::new();
+        tag.set_track_gain(-6.0);
+        tag.set_album_gain(-4.0);
+
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .version(Version::Id3v23)
+            .build()
+            .unwrap()
+            .encode(&tag, &mut buffer)
+            .unwrap();
+        let tag_v3 = decode(&mut io::Cursor::new(buffer)).unwrap();
+        assert!(tag_v3
+            .get("RVAD")
+            .unwrap()
+            .content()
+            .volume_adjustment()
+            .is_some());
+        // The album gain has no ID3v2.3 equivalent and is omitted.
+        assert_eq!(1, tag_v3.frames().count());
+        assert!((tag_v3.track_gain().unwrap() + 6.0).abs() < 0.02);
+        assert_eq!(None, tag_v3.album_gain());
+
+        let mut buffer = Vec::new();
+        EncoderBuilder::default()
+            .version(Version::Id3v24)
+            .build()
+            .unwrap()
+            .encode(&tag_v3, &mut buffer)
+            .unwrap();
+        let tag_v4 = decode(&mut io::Cursor::new(buffer)).unwrap();
+        assert!(tag_v4.get("RVAD").is_none());
+        assert_eq!(1, tag_v4.relative_volume_adjustments().count());
+        assert!((tag_v4.track_gain().unwrap() + 6.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn write_equalisation_conversion() {
+        use crate::frame::{Equalisation, InterpolationMethod};
+
+        for version in &[Version::Id3v22, Version::Id3v23] {
+            // ID3v2.2 has no equivalent of EQU2 frames that can not be converted.
+            let identifications: &[&str] = match version {
+                Version::Id3v22 => &[""],
+                _ => &["", "car"],
+            };
+            let mut tag = Tag::new();
+            for identification in identifications {
+                tag.add_frame(Frame::with_content(
+                    "EQU2",
+                    Content::Equalisation(Equalisation {
+                        interpolation_method: InterpolationMethod::Band,
+                        identification: identification.to_string(),
+                        adjustments: vec![(200, -1024), (20_000, 512)],
+                    }),
+                ));
+            }
+
+            let mut buffer = Vec::new();
+            tag.write_to(&mut buffer, *version).unwrap();
+            let tag_v3 = decode(&mut io::Cursor::new(buffer)).unwrap();
+            let equa = tag_v3
+                .get("EQUA")
+                .unwrap()
+                .content()
+                .legacy_equalisation()
+                .unwrap();
+            assert_eq!(
+                vec![100, 10_000],
+                equa.adjustments.iter().map(|a| a.0).collect::<Vec<_>>()
+            );
+            // Only the equalisation without identification has an ID3v2.2/ID3v2.3 equivalent.
+            if *version == Version::Id3v23 {
+                assert_eq!(
+                    "car",
+                    tag_v3
+                        .get("EQU2")
+                        .unwrap()
+                        .content()
+                        .equalisation()
+                        .unwrap()
+                        .identification
+                );
+            }
+
+            let mut buffer = Vec::new();
+            tag_v3.write_to(&mut buffer, Version::Id3v24).unwrap();
+            let tag_v4 = decode(&mut io::Cursor::new(buffer)).unwrap();
+            assert!(tag_v4.get("EQUA").is_none());
+            let equ2 = tag_v4
+                .frames()
+                .filter_map(|frame| frame.content().equalisation())
+                .find(|equ2| equ2.identification.is_empty())
+                .unwrap();
+            // The conversion is lossy, the adjustments are accurate to a few 1/512 dB.
+            for (&(frequency, adjustment), &expected) in
+                equ2.adjustments.iter().zip(&[(200, -1024), (20_000, 512)])
+            {
+                assert_eq!(expected.0, frequency);
+                assert!((i32::from(adjustment) - expected.1).abs() <= 8);
+            }
+        }
+    }
 }
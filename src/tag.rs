@@ -1,13 +1,19 @@
+use crate::extended_header::ExtendedHeader;
+use crate::frame::Ciphers;
 use crate::frame::Content;
 use crate::frame::{
-    Comment, ExtendedLink, ExtendedText, Frame, Lyrics, Picture, PictureType, SynchronisedLyrics,
-    Timestamp,
+    ChannelAdjustment, ChannelType, Chapter, Comment, Commercial, EncapsulatedObject, ExtendedLink,
+    ExtendedText, Frame, LinkedInformation, Lyrics, MusicCdIdentifier, Ownership, Picture,
+    PictureType, Popularimeter, Private, RelativeVolumeAdjustment, SynchronisedLyrics,
+    TableOfContents, TermsOfUse, Timestamp, UniqueFileIdentifier,
 };
 use crate::storage::{self, PlainStorage, Storage};
 use crate::stream;
 use crate::v1;
+use crate::{Error, ErrorKind};
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
-use std::io::{self, BufReader, Write};
+use std::io::{self, BufReader, Seek, Write};
 use std::iter::Iterator;
 use std::path::Path;
 
@@ -41,11 +47,137 @@ impl Version {
     }
 }
 
+/// Denotes how `Tag::read_from_merged` combines the tags that are found in a file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MergePolicy {
+    /// Frames from the ID3v2 tag take precedence. The ID3v1 tag is only used to fill in the
+    /// frames that are missing from the ID3v2 tag.
+    PreferId3v2,
+    /// Fields from the ID3v1 tag replace their ID3v2 counterparts.
+    PreferId3v1,
+}
+
+/// Denotes the kind of tag a frame was read from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TagSource {
+    /// The frame was read from the ID3v2 tag.
+    Id3v2,
+    /// The frame was converted from an ID3v1 field.
+    Id3v1,
+    /// The frame was converted from an ID3v1 field which includes data from the TAG+ extension.
+    Id3v1Extended,
+}
+
+/// ReplayGain values, as read by `Tag::replay_gain`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ReplayGain {
+    /// The gain to apply to the track in dB.
+    pub track_gain: Option<f32>,
+    /// The peak amplitude of the track relative to full scale.
+    pub track_peak: Option<f32>,
+    /// The gain to apply to the album in dB.
+    pub album_gain: Option<f32>,
+    /// The peak amplitude of the album relative to full scale.
+    pub album_peak: Option<f32>,
+}
+
+/// Denotes a convention for storing ReplayGain values in a tag.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ReplayGainFormat {
+    /// User defined text frames (TXXX) with the descriptions REPLAYGAIN_TRACK_GAIN,
+    /// REPLAYGAIN_TRACK_PEAK, REPLAYGAIN_ALBUM_GAIN and REPLAYGAIN_ALBUM_PEAK, as written by
+    /// foobar2000.
+    ExtendedText,
+    /// Relative volume adjustment frames (RVA2) with the identifications "track" and "album".
+    ///
+    /// When written as ID3v2.3, only the track values are kept, as RVAD.
+    RelativeVolumeAdjustment,
+    /// The iTunNORM comment (COMM) written by iTunes. Only the track values can be stored.
+    ITunesNormalization,
+}
+
+const REPLAYGAIN_TRACK_GAIN: &str = "REPLAYGAIN_TRACK_GAIN";
+const REPLAYGAIN_TRACK_PEAK: &str = "REPLAYGAIN_TRACK_PEAK";
+const REPLAYGAIN_ALBUM_GAIN: &str = "REPLAYGAIN_ALBUM_GAIN";
+const REPLAYGAIN_ALBUM_PEAK: &str = "REPLAYGAIN_ALBUM_PEAK";
+const ITUNES_NORMALIZATION: &str = "iTunNORM";
+
+/// Parses a ReplayGain value such as "-6.50 dB" or "0.988831".
+fn parse_replay_gain_value(value: &str) -> Option<f32> {
+    value
+        .trim()
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Parses the track gain and peak from the text of an iTunNORM comment.
+///
+/// The comment consists of ten hexadecimal numbers. The first two hold the loudness of the left
+/// and right channels relative to a reference of 1000, the seventh and eighth hold the peak
+/// sample values of the channels.
+fn parse_itunes_normalization(text: &str) -> (Option<f32>, Option<f32>) {
+    let values = text
+        .split_whitespace()
+        .map(|value| u32::from_str_radix(value, 16))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_default();
+    if values.len() < 8 {
+        return (None, None);
+    }
+    let loudness = values[0].max(values[1]);
+    let gain = if loudness > 0 {
+        Some((-10.0 * (f64::from(loudness) / 1000.0).log10()) as f32)
+    } else {
+        None
+    };
+    let peak = values[6].max(values[7]);
+    let peak = if peak > 0 {
+        Some(peak as f32 / 32768.0)
+    } else {
+        None
+    };
+    (gain, peak)
+}
+
+/// Formats the track gain and peak as the text of an iTunNORM comment.
+fn format_itunes_normalization(gain: f32, peak: Option<f32>) -> String {
+    let loudness = |reference: f64| {
+        (reference * 10f64.powf(-f64::from(gain) / 10.0))
+            .round()
+            .min(f64::from(u32::MAX)) as u32
+    };
+    let peak = peak
+        .map(|peak| (f64::from(peak) * 32768.0).round().min(f64::from(u32::MAX)) as u32)
+        .unwrap_or(0);
+    [
+        loudness(1000.0),
+        loudness(1000.0),
+        loudness(2500.0),
+        loudness(2500.0),
+        0,
+        0,
+        peak,
+        peak,
+        0,
+        0,
+    ]
+    .iter()
+    .map(|value| format!(" {:08X}", value))
+    .collect()
+}
+
 /// An ID3 tag containing metadata frames.
 #[derive(Clone, Debug, Default, Eq)]
 pub struct Tag {
     /// A vector of frames included in the tag.
     frames: Vec<Frame>,
+    /// The extended header of the tag, if any.
+    extended_header: Option<ExtendedHeader>,
+    /// Whether the frames of the tag that a SEEK frame refers to were merged into this tag when
+    /// it was read. Writing the tag to a file then replaces that tag as well.
+    seek_target_merged: bool,
 }
 
 impl<'a> Tag {
@@ -147,6 +279,195 @@ impl<'a> Tag {
         self.frames().filter_map(|frame| frame.content().picture())
     }
 
+    /// Returns an iterator over the unique file identifiers (UFID) in the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_unique_file_identifier("http://example.com", b"1234".to_vec());
+    /// tag.set_unique_file_identifier("http://example.org", b"5678".to_vec());
+    ///
+    /// assert_eq!(tag.unique_file_identifiers().count(), 2);
+    /// ```
+    pub fn unique_file_identifiers(
+        &'a self,
+    ) -> impl Iterator<Item = &'a UniqueFileIdentifier> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().unique_file_identifier())
+    }
+
+    /// Returns an iterator over the general encapsulated objects (GEOB) in the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::EncapsulatedObject;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_encapsulated_object(EncapsulatedObject {
+    ///     mime_type: "application/octet-stream".to_string(),
+    ///     filename: "".to_string(),
+    ///     description: "Serato Markers2".to_string(),
+    ///     data: vec![0x01, 0x01],
+    /// });
+    ///
+    /// assert_eq!(tag.encapsulated_objects().count(), 1);
+    /// ```
+    pub fn encapsulated_objects(&'a self) -> impl Iterator<Item = &'a EncapsulatedObject> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().encapsulated_object())
+    }
+
+    /// Returns an iterator over the private frames (PRIV) in the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_private("WM/MediaClassPrimaryID", vec![0xBC, 0x7D]);
+    /// tag.set_private("http://example.com", vec![1, 2, 3]);
+    ///
+    /// assert_eq!(tag.private_frames().count(), 2);
+    /// ```
+    pub fn private_frames(&'a self) -> impl Iterator<Item = &'a Private> + 'a {
+        self.frames().filter_map(|frame| frame.content().private())
+    }
+
+    /// Returns an iterator over the tables of contents (CTOC) in the tag.
+    pub fn tables_of_contents(&'a self) -> impl Iterator<Item = &'a TableOfContents> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().table_of_contents())
+    }
+
+    /// Returns the chapters (CHAP) in the tag in the order of the table of contents.
+    ///
+    /// The tree of tables of contents (CTOC) is walked depth first, starting at the top level
+    /// table of contents. The elements of a table of contents that is not ordered are sorted by
+    /// their start time. Chapters that are not part of the tree are left out. If the tag has no
+    /// top level table of contents, all chapters are returned ordered by their start time.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::{Chapter, Content, Frame, TableOfContents};
+    ///
+    /// let mut tag = Tag::new();
+    /// for &(id, start, end) in &[("ch2", 1000, 2000), ("ch1", 0, 1000)] {
+    ///     tag.add_frame(Frame::with_content("CHAP", Content::Chapter(Chapter {
+    ///         element_id: id.to_string(),
+    ///         start_time: start,
+    ///         end_time: end,
+    ///         start_offset: None,
+    ///         end_offset: None,
+    ///         frames: vec![Frame::with_content("TIT2", Content::Text(id.to_string()))],
+    ///     })));
+    /// }
+    /// tag.add_frame(Frame::with_content("CTOC", Content::TableOfContents(TableOfContents {
+    ///     element_id: "toc".to_string(),
+    ///     top_level: true,
+    ///     ordered: true,
+    ///     elements: vec!["ch1".to_string(), "ch2".to_string()],
+    ///     frames: Vec::new(),
+    /// })));
+    ///
+    /// let chapters = tag.chapters();
+    /// assert_eq!(chapters.len(), 2);
+    /// assert_eq!(chapters[0].element_id, "ch1");
+    /// assert_eq!(chapters[1].element_id, "ch2");
+    /// ```
+    pub fn chapters(&self) -> Vec<&Chapter> {
+        let chapters: HashMap<&str, &Chapter> = self
+            .frames()
+            .filter_map(|frame| frame.content().chapter())
+            .map(|chapter| (&chapter.element_id[..], chapter))
+            .collect();
+        let root = match self.tables_of_contents().find(|toc| toc.top_level) {
+            Some(root) => root,
+            None => {
+                let mut ordered: Vec<&Chapter> = chapters.values().cloned().collect();
+                ordered.sort_by_key(|chapter| chapter.start_time);
+                return ordered;
+            }
+        };
+        let tables: HashMap<&str, &TableOfContents> = self
+            .tables_of_contents()
+            .map(|toc| (&toc.element_id[..], toc))
+            .collect();
+
+        fn walk<'t>(
+            toc: &'t TableOfContents,
+            chapters: &HashMap<&str, &'t Chapter>,
+            tables: &HashMap<&str, &'t TableOfContents>,
+            visited: &mut HashSet<&'t str>,
+            ordered: &mut Vec<&'t Chapter>,
+        ) {
+            // Guard against tables of contents that refer to each other.
+            if !visited.insert(&toc.element_id) {
+                return;
+            }
+            let start = ordered.len();
+            for element_id in &toc.elements {
+                if let Some(chapter) = chapters.get(&element_id[..]) {
+                    ordered.push(chapter);
+                } else if let Some(child) = tables.get(&element_id[..]) {
+                    walk(child, chapters, tables, visited, ordered);
+                }
+            }
+            if !toc.ordered {
+                ordered[start..].sort_by_key(|chapter| chapter.start_time);
+            }
+        }
+        let mut ordered = Vec::new();
+        walk(root, &chapters, &tables, &mut HashSet::new(), &mut ordered);
+        ordered
+    }
+
+    /// Returns an iterator over the relative volume adjustments (RVA2) in the tag.
+    pub fn relative_volume_adjustments(
+        &'a self,
+    ) -> impl Iterator<Item = &'a RelativeVolumeAdjustment> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().relative_volume_adjustment())
+    }
+
+    /// Returns an iterator over the popularimeters (POPM) in the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_rating("alice@example.com", 196);
+    /// tag.set_rating("bob@example.com", 64);
+    ///
+    /// assert_eq!(tag.popularimeters().count(), 2);
+    /// ```
+    pub fn popularimeters(&'a self) -> impl Iterator<Item = &'a Popularimeter> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().popularimeter())
+    }
+
+    /// Returns an iterator over the terms of use (USER) in the tag.
+    pub fn terms_of_use(&'a self) -> impl Iterator<Item = &'a TermsOfUse> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().terms_of_use())
+    }
+
+    /// Returns an iterator over the commercial frames (COMR) in the tag.
+    pub fn commercial_frames(&'a self) -> impl Iterator<Item = &'a Commercial> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().commercial())
+    }
+
+    /// Returns an iterator over the linked information frames (LINK) in the tag.
+    pub fn linked_information(&'a self) -> impl Iterator<Item = &'a LinkedInformation> + 'a {
+        self.frames()
+            .filter_map(|frame| frame.content().linked_information())
+    }
+
     /// Returns a reference to the first frame with the specified identifier.
     ///
     /// # Example
@@ -222,150 +543,1016 @@ impl<'a> Tag {
         self.frames.retain(|frame| frame.id() != id.as_ref());
     }
 
-    /// Adds a user defined text frame (TXXX).
+    /// Adds a user defined text frame (TXXX).
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    ///
+    /// tag.add_extended_text("key1", "value1");
+    /// tag.add_extended_text("key2", "value2");
+    ///
+    /// assert_eq!(tag.extended_texts().count(), 2);
+    /// assert!(tag.extended_texts().any(|t| t.description == "key1" && t.value == "value1"));
+    /// assert!(tag.extended_texts().any(|t| t.description == "key2" && t.value == "value2"));
+    /// ```
+    pub fn add_extended_text(&mut self, description: impl Into<String>, value: impl Into<String>) {
+        let frame = Frame::with_content(
+            "TXXX",
+            Content::ExtendedText(ExtendedText {
+                description: description.into(),
+                value: value.into(),
+            }),
+        );
+        self.add_frame(frame);
+    }
+
+    /// Removes the user defined text frame (TXXX) with the specified key and value.
+    ///
+    /// A key or value may be `None` to specify a wildcard value.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    ///
+    /// tag.add_extended_text("key1", "value1");
+    /// tag.add_extended_text("key2", "value2");
+    /// tag.add_extended_text("key3", "value2");
+    /// tag.add_extended_text("key4", "value3");
+    /// tag.add_extended_text("key5", "value4");
+    /// assert_eq!(tag.extended_texts().count(), 5);
+    ///
+    /// tag.remove_extended_text(Some("key1"), None);
+    /// assert_eq!(tag.extended_texts().count(), 4);
+    ///
+    /// tag.remove_extended_text(None, Some("value2"));
+    /// assert_eq!(tag.extended_texts().count(), 2);
+    ///
+    /// tag.remove_extended_text(Some("key4"), Some("value3"));
+    /// assert_eq!(tag.extended_texts().count(), 1);
+    ///
+    /// tag.remove_extended_text(None, None);
+    /// assert_eq!(tag.extended_texts().count(), 0);
+    /// ```
+    pub fn remove_extended_text(&mut self, description: Option<&str>, value: Option<&str>) {
+        self.frames.retain(|frame| {
+            if frame.id() == "TXXX" {
+                match *frame.content() {
+                    Content::ExtendedText(ref ext) => {
+                        let descr_match = description.map(|v| v == ext.description).unwrap_or(true);
+                        let value_match = value.map(|v| v == ext.value).unwrap_or(true);
+                        // True if we want to keep the frame.
+                        !(descr_match && value_match)
+                    }
+                    _ => {
+                        // A TXXX frame must always have content of the ExtendedText type. Remove
+                        // frames that do not fit this requirement.
+                        false
+                    }
+                }
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Adds a picture frame (APIC).
+    /// Any other pictures with the same type will be removed from the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::{Picture, PictureType};
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_picture(Picture {
+    ///     mime_type: "image/jpeg".to_string(),
+    ///     picture_type: PictureType::Other,
+    ///     description: "some image".to_string(),
+    ///     data: vec![],
+    /// });
+    /// tag.add_picture(Picture {
+    ///     mime_type: "image/png".to_string(),
+    ///     picture_type: PictureType::Other,
+    ///     description: "some other image".to_string(),
+    ///     data: vec![],
+    /// });
+    /// assert_eq!(tag.pictures().count(), 1);
+    /// assert_eq!(&tag.pictures().nth(0).unwrap().mime_type[..], "image/png");
+    /// ```
+    pub fn add_picture(&mut self, picture: Picture) {
+        let frame = Frame::with_content("APIC", Content::Picture(picture));
+        self.add_frame(frame);
+    }
+
+    /// Removes all pictures of the specified type.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::{Picture, PictureType};
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_picture(Picture {
+    ///     mime_type: "image/jpeg".to_string(),
+    ///     picture_type: PictureType::Other,
+    ///     description: "some image".to_string(),
+    ///     data: vec![],
+    /// });
+    /// tag.add_picture(Picture {
+    ///     mime_type: "image/png".to_string(),
+    ///     picture_type: PictureType::CoverFront,
+    ///     description: "some other image".to_string(),
+    ///     data: vec![],
+    /// });
+    ///
+    /// assert_eq!(tag.pictures().count(), 2);
+    /// tag.remove_picture_by_type(PictureType::CoverFront);
+    /// assert_eq!(tag.pictures().count(), 1);
+    /// assert_eq!(tag.pictures().nth(0).unwrap().picture_type, PictureType::Other);
+    /// ```
+    pub fn remove_picture_by_type(&mut self, picture_type: PictureType) {
+        self.frames.retain(|frame| {
+            if frame.id() == "APIC" {
+                let pic = match *frame.content() {
+                    Content::Picture(ref picture) => picture,
+                    _ => return false,
+                };
+                return pic.picture_type != picture_type;
+            }
+
+            true
+        });
+    }
+
+    /// Returns the unique file identifier (UFID) for the specified owner.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_unique_file_identifier("http://example.com", b"1234".to_vec());
+    ///
+    /// assert_eq!(tag.unique_file_identifier("http://example.com"), Some(&b"1234"[..]));
+    /// assert_eq!(tag.unique_file_identifier("http://example.org"), None);
+    /// ```
+    pub fn unique_file_identifier(&self, owner_identifier: &str) -> Option<&[u8]> {
+        self.unique_file_identifiers()
+            .find(|ufid| ufid.owner_identifier == owner_identifier)
+            .map(|ufid| &ufid.identifier[..])
+    }
+
+    /// Sets the unique file identifier (UFID) for the specified owner.
+    ///
+    /// Any other identifier with the same owner will be removed from the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_unique_file_identifier("http://example.com", b"1234".to_vec());
+    /// tag.set_unique_file_identifier("http://example.com", b"5678".to_vec());
+    ///
+    /// assert_eq!(tag.unique_file_identifiers().count(), 1);
+    /// assert_eq!(tag.unique_file_identifier("http://example.com"), Some(&b"5678"[..]));
+    /// ```
+    pub fn set_unique_file_identifier(
+        &mut self,
+        owner_identifier: impl Into<String>,
+        identifier: impl Into<Vec<u8>>,
+    ) {
+        let frame = Frame::with_content(
+            "UFID",
+            Content::UniqueFileIdentifier(UniqueFileIdentifier {
+                owner_identifier: owner_identifier.into(),
+                identifier: identifier.into(),
+            }),
+        );
+        self.add_frame(frame);
+    }
+
+    /// Removes the unique file identifier (UFID) for the specified owner.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_unique_file_identifier("http://example.com", b"1234".to_vec());
+    /// tag.set_unique_file_identifier("http://example.org", b"5678".to_vec());
+    ///
+    /// tag.remove_unique_file_identifier("http://example.com");
+    /// assert_eq!(tag.unique_file_identifiers().count(), 1);
+    /// assert_eq!(tag.unique_file_identifier("http://example.com"), None);
+    /// ```
+    pub fn remove_unique_file_identifier(&mut self, owner_identifier: &str) {
+        self.frames.retain(|frame| match *frame.content() {
+            Content::UniqueFileIdentifier(ref ufid) => ufid.owner_identifier != owner_identifier,
+            _ => true,
+        });
+    }
+
+    /// Adds a general encapsulated object (GEOB) to the tag.
+    ///
+    /// Any other object with the same description will be removed from the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::EncapsulatedObject;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_encapsulated_object(EncapsulatedObject {
+    ///     mime_type: "application/octet-stream".to_string(),
+    ///     filename: "".to_string(),
+    ///     description: "Serato Markers2".to_string(),
+    ///     data: vec![0x01, 0x01],
+    /// });
+    /// tag.add_encapsulated_object(EncapsulatedObject {
+    ///     mime_type: "application/octet-stream".to_string(),
+    ///     filename: "".to_string(),
+    ///     description: "Serato Markers2".to_string(),
+    ///     data: vec![0x01, 0x01, 0x00],
+    /// });
+    ///
+    /// assert_eq!(tag.encapsulated_objects().count(), 1);
+    /// assert_eq!(tag.encapsulated_objects().nth(0).unwrap().data.len(), 3);
+    /// ```
+    pub fn add_encapsulated_object(&mut self, object: EncapsulatedObject) {
+        let frame = Frame::with_content("GEOB", Content::EncapsulatedObject(object));
+        self.add_frame(frame);
+    }
+
+    /// Adds the contents of a file as a general encapsulated object (GEOB). The filename of the
+    /// object is set to the name of the file.
+    ///
+    /// Any other object with the same description will be removed from the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.attach_file("testdata/image.jpg", "image/jpeg", "artwork source").unwrap();
+    ///
+    /// let object = tag.encapsulated_object("artwork source").unwrap();
+    /// assert_eq!(object.filename, "image.jpg");
+    /// assert_eq!(object.mime_type, "image/jpeg");
+    /// ```
+    pub fn attach_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        mime_type: impl Into<String>,
+        description: impl Into<String>,
+    ) -> crate::Result<()> {
+        let path = path.as_ref();
+        let data = fs::read(path)?;
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.add_encapsulated_object(EncapsulatedObject {
+            mime_type: mime_type.into(),
+            filename,
+            description: description.into(),
+            data,
+        });
+        Ok(())
+    }
+
+    /// Returns the general encapsulated object (GEOB) with the specified description.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::EncapsulatedObject;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_encapsulated_object(EncapsulatedObject {
+    ///     mime_type: "application/octet-stream".to_string(),
+    ///     filename: "".to_string(),
+    ///     description: "Serato Markers2".to_string(),
+    ///     data: vec![0x01, 0x01],
+    /// });
+    ///
+    /// assert!(tag.encapsulated_object("Serato Markers2").is_some());
+    /// assert!(tag.encapsulated_object("Serato Autotags").is_none());
+    /// ```
+    pub fn encapsulated_object(&self, description: &str) -> Option<&EncapsulatedObject> {
+        self.encapsulated_objects()
+            .find(|object| object.description == description)
+    }
+
+    /// Writes the data of the general encapsulated object (GEOB) with the specified description
+    /// to a file.
+    ///
+    /// Returns an error with `ErrorKind::InvalidInput` if the tag contains no such object.
+    pub fn extract_encapsulated_object(
+        &self,
+        description: &str,
+        path: impl AsRef<Path>,
+    ) -> crate::Result<()> {
+        let object = self.encapsulated_object(description).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "the tag contains no encapsulated object with the description",
+            )
+        })?;
+        fs::write(path, &object.data)?;
+        Ok(())
+    }
+
+    /// Removes the general encapsulated object (GEOB) with the specified description.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.attach_file("testdata/image.jpg", "image/jpeg", "artwork source").unwrap();
+    /// tag.remove_encapsulated_object("artwork source");
+    /// assert_eq!(tag.encapsulated_objects().count(), 0);
+    /// ```
+    pub fn remove_encapsulated_object(&mut self, description: &str) {
+        self.frames.retain(|frame| match *frame.content() {
+            Content::EncapsulatedObject(ref object) => object.description != description,
+            _ => true,
+        });
+    }
+
+    /// Returns the data of the first private frame (PRIV) of the specified owner.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_private("http://example.com", vec![1, 2, 3]);
+    ///
+    /// assert_eq!(tag.private("http://example.com"), Some(&[1, 2, 3][..]));
+    /// assert_eq!(tag.private("http://example.org"), None);
+    /// ```
+    pub fn private(&self, owner_identifier: &str) -> Option<&[u8]> {
+        self.private_frames()
+            .find(|private| private.owner_identifier == owner_identifier)
+            .map(|private| &private.data[..])
+    }
+
+    /// Sets the data of the private frame (PRIV) of the specified owner.
+    ///
+    /// Any other private frames of the same owner will be removed from the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_private("http://example.com", vec![1, 2, 3]);
+    /// tag.set_private("http://example.com", vec![4, 5, 6]);
+    ///
+    /// assert_eq!(tag.private_frames().count(), 1);
+    /// assert_eq!(tag.private("http://example.com"), Some(&[4, 5, 6][..]));
+    /// ```
+    pub fn set_private(&mut self, owner_identifier: impl Into<String>, data: impl Into<Vec<u8>>) {
+        let owner_identifier = owner_identifier.into();
+        self.remove_private(&owner_identifier);
+        let frame = Frame::with_content(
+            "PRIV",
+            Content::Private(Private {
+                owner_identifier,
+                data: data.into(),
+            }),
+        );
+        self.add_frame(frame);
+    }
+
+    /// Removes all private frames (PRIV) of the specified owner.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_private("WM/MediaClassPrimaryID", vec![0xBC, 0x7D]);
+    /// tag.set_private("http://example.com", vec![1, 2, 3]);
+    ///
+    /// tag.remove_private("WM/MediaClassPrimaryID");
+    /// assert_eq!(tag.private_frames().count(), 1);
+    /// assert_eq!(tag.private("WM/MediaClassPrimaryID"), None);
+    /// ```
+    pub fn remove_private(&mut self, owner_identifier: &str) {
+        self.frames.retain(|frame| match *frame.content() {
+            Content::Private(ref private) => private.owner_identifier != owner_identifier,
+            _ => true,
+        });
+    }
+
+    /// Returns the music CD identifier (MCDI).
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::MusicCdIdentifier;
+    ///
+    /// let mut tag = Tag::new();
+    /// assert!(tag.music_cd_identifier().is_none());
+    /// tag.set_music_cd_identifier(MusicCdIdentifier::from_offsets(1, &[150, 15363], 32314));
+    /// assert_eq!(tag.music_cd_identifier().unwrap().freedb_disc_id(), Some(0x0801_ac02));
+    /// ```
+    pub fn music_cd_identifier(&self) -> Option<&MusicCdIdentifier> {
+        self.get("MCDI")
+            .and_then(|frame| frame.content().music_cd_identifier())
+    }
+
+    /// Sets the music CD identifier (MCDI).
+    pub fn set_music_cd_identifier(&mut self, mcdi: MusicCdIdentifier) {
+        self.add_frame(Frame::with_content(
+            "MCDI",
+            Content::MusicCdIdentifier(mcdi),
+        ));
+    }
+
+    /// Removes the music CD identifier (MCDI).
+    pub fn remove_music_cd_identifier(&mut self) {
+        self.remove("MCDI");
+    }
+
+    /// Returns the master volume adjustment in dB of the relative volume adjustment (RVA2) with
+    /// the specified identification.
+    fn volume_gain(&self, identification: &str) -> Option<f32> {
+        self.relative_volume_adjustments()
+            .find(|rva2| rva2.identification.eq_ignore_ascii_case(identification))
+            .and_then(|rva2| rva2.channel(ChannelType::MasterVolume))
+            .map(|channel| channel.gain())
+    }
+
+    /// Sets the master volume adjustment in dB of the relative volume adjustment (RVA2) with the
+    /// specified identification, retaining the adjustments of other channels.
+    fn set_volume_gain(&mut self, identification: &str, gain: f32) {
+        let mut rva2 = self
+            .relative_volume_adjustments()
+            .find(|rva2| rva2.identification.eq_ignore_ascii_case(identification))
+            .cloned()
+            .unwrap_or_else(|| RelativeVolumeAdjustment {
+                identification: identification.to_string(),
+                channels: Vec::new(),
+            });
+        match rva2
+            .channels
+            .iter_mut()
+            .find(|channel| channel.channel_type == ChannelType::MasterVolume)
+        {
+            Some(channel) => channel.set_gain(gain),
+            None => rva2.channels.push(ChannelAdjustment::with_gain(
+                ChannelType::MasterVolume,
+                gain,
+            )),
+        }
+        self.add_frame(Frame::with_content(
+            "RVA2",
+            Content::RelativeVolumeAdjustment(rva2),
+        ));
+    }
+
+    /// Returns the track gain in dB.
+    ///
+    /// The gain is read from the master volume of the relative volume adjustment (RVA2) with the
+    /// identification "track". If there is no such frame, the average of the left and right
+    /// channels of an ID3v2.3 relative volume adjustment (RVAD) is used.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// assert!(tag.track_gain().is_none());
+    /// tag.set_track_gain(-6.5);
+    /// assert_eq!(tag.track_gain(), Some(-6.5));
+    /// ```
+    pub fn track_gain(&self) -> Option<f32> {
+        self.volume_gain("track")
+            .or_else(|| self.legacy_volume_gain())
+    }
+
+    /// Returns the average gain in dB of the left and right channels of the ID3v2.3 relative
+    /// volume adjustment (RVAD).
+    fn legacy_volume_gain(&self) -> Option<f32> {
+        self.frames()
+            .filter_map(|frame| frame.content().volume_adjustment())
+            .map(|rvad| (rvad.right.gain() + rvad.left.gain()) / 2.0)
+            .next()
+    }
+
+    /// Sets the track gain in dB.
+    ///
+    /// The gain is stored as the master volume of the relative volume adjustment (RVA2) with the
+    /// identification "track", which is converted to RVAD when the tag is written as ID3v2.3.
+    /// Any existing RVAD frame is removed.
+    pub fn set_track_gain(&mut self, gain: f32) {
+        self.frames
+            .retain(|frame| frame.content().volume_adjustment().is_none());
+        self.set_volume_gain("track", gain);
+    }
+
+    /// Returns the album gain in dB.
+    ///
+    /// The gain is read from the master volume of the relative volume adjustment (RVA2) with the
+    /// identification "album".
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_track_gain(-6.5);
+    /// tag.set_album_gain(-4.25);
+    /// assert_eq!(tag.album_gain(), Some(-4.25));
+    /// assert_eq!(tag.relative_volume_adjustments().count(), 2);
+    /// ```
+    pub fn album_gain(&self) -> Option<f32> {
+        self.volume_gain("album")
+    }
+
+    /// Sets the album gain in dB.
+    ///
+    /// The gain is stored as the master volume of the relative volume adjustment (RVA2) with the
+    /// identification "album". ID3v2.2 and ID3v2.3 have no equivalent, so the frame is omitted when
+    /// the tag is written as one of those versions.
+    pub fn set_album_gain(&mut self, gain: f32) {
+        self.set_volume_gain("album", gain);
+    }
+
+    /// Returns the ReplayGain values of the tag or None if the tag has none.
+    ///
+    /// Each value is read from the first of the following representations that holds it:
+    ///
+    /// 1. User defined text frames (TXXX) with descriptions such as REPLAYGAIN_TRACK_GAIN, which
+    ///    are compared case insensitively.
+    /// 2. The master volume of relative volume adjustment frames (RVA2) with the identifications
+    ///    "track" and "album".
+    /// 3. The ID3v2.3 relative volume adjustment (RVAD), which is the equivalent of the RVA2 frame
+    ///    with the identification "track". Only the track gain is read from it, as its peak
+    ///    volumes have no defined scale.
+    /// 4. The iTunNORM comment (COMM), which only holds the track values.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_extended_text("replaygain_track_gain", "-6.50 dB");
+    /// tag.add_extended_text("REPLAYGAIN_TRACK_PEAK", "0.988831");
+    /// tag.set_album_gain(-4.25);
+    ///
+    /// let replay_gain = tag.replay_gain().unwrap();
+    /// assert_eq!(replay_gain.track_gain, Some(-6.5));
+    /// assert_eq!(replay_gain.track_peak, Some(0.988831));
+    /// assert_eq!(replay_gain.album_gain, Some(-4.25));
+    /// assert_eq!(replay_gain.album_peak, None);
+    /// ```
+    pub fn replay_gain(&self) -> Option<ReplayGain> {
+        let extended_text = |description: &str| {
+            self.extended_texts()
+                .find(|ext| ext.description.eq_ignore_ascii_case(description))
+                .and_then(|ext| parse_replay_gain_value(&ext.value))
+        };
+        let master_volume = |identification: &str| {
+            self.relative_volume_adjustments()
+                .find(|rva2| rva2.identification.eq_ignore_ascii_case(identification))
+                .and_then(|rva2| rva2.channel(ChannelType::MasterVolume))
+        };
+        let (itunes_gain, itunes_peak) = self
+            .comments()
+            .find(|comment| comment.description == ITUNES_NORMALIZATION)
+            .map(|comment| parse_itunes_normalization(&comment.text))
+            .unwrap_or((None, None));
+
+        let replay_gain = ReplayGain {
+            track_gain: extended_text(REPLAYGAIN_TRACK_GAIN)
+                .or_else(|| master_volume("track").map(|channel| channel.gain()))
+                .or_else(|| self.legacy_volume_gain())
+                .or(itunes_gain),
+            track_peak: extended_text(REPLAYGAIN_TRACK_PEAK)
+                .or_else(|| {
+                    master_volume("track")
+                        .and_then(|channel| channel.peak_amplitude())
+                        .map(|peak| peak as f32)
+                })
+                .or(itunes_peak),
+            album_gain: extended_text(REPLAYGAIN_ALBUM_GAIN)
+                .or_else(|| master_volume("album").map(|channel| channel.gain())),
+            album_peak: extended_text(REPLAYGAIN_ALBUM_PEAK).or_else(|| {
+                master_volume("album")
+                    .and_then(|channel| channel.peak_amplitude())
+                    .map(|peak| peak as f32)
+            }),
+        };
+        if replay_gain == ReplayGain::default() {
+            None
+        } else {
+            Some(replay_gain)
+        }
+    }
+
+    /// Sets the ReplayGain values using the specified formats.
+    ///
+    /// All existing ReplayGain values are removed first, including those stored in formats that
+    /// are not written, so they can not take precedence over the new values.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::{ReplayGain, ReplayGainFormat, Tag};
+    ///
+    /// let mut tag = Tag::new();
+    /// let replay_gain = ReplayGain {
+    ///     track_gain: Some(-6.5),
+    ///     track_peak: Some(0.5),
+    ///     album_gain: Some(-4.25),
+    ///     album_peak: None,
+    /// };
+    /// tag.set_replay_gain(
+    ///     replay_gain,
+    ///     &[ReplayGainFormat::ExtendedText, ReplayGainFormat::RelativeVolumeAdjustment],
+    /// );
+    ///
+    /// assert_eq!(tag.replay_gain(), Some(replay_gain));
+    /// assert_eq!(tag.extended_texts().count(), 3);
+    /// assert_eq!(tag.relative_volume_adjustments().count(), 2);
+    /// ```
+    pub fn set_replay_gain(&mut self, replay_gain: ReplayGain, formats: &[ReplayGainFormat]) {
+        self.remove_replay_gain();
+        let values = [
+            ("track", replay_gain.track_gain, replay_gain.track_peak),
+            ("album", replay_gain.album_gain, replay_gain.album_peak),
+        ];
+        for format in formats {
+            match format {
+                ReplayGainFormat::ExtendedText => {
+                    for &(name, gain, peak) in &values {
+                        if let Some(gain) = gain {
+                            self.add_extended_text(
+                                format!("REPLAYGAIN_{}_GAIN", name.to_uppercase()),
+                                format!("{:.2} dB", gain),
+                            );
+                        }
+                        if let Some(peak) = peak {
+                            self.add_extended_text(
+                                format!("REPLAYGAIN_{}_PEAK", name.to_uppercase()),
+                                format!("{:.6}", peak),
+                            );
+                        }
+                    }
+                }
+                ReplayGainFormat::RelativeVolumeAdjustment => {
+                    for &(identification, gain, peak) in &values {
+                        let gain = match gain {
+                            Some(gain) => gain,
+                            None => continue,
+                        };
+                        let mut channel =
+                            ChannelAdjustment::with_gain(ChannelType::MasterVolume, gain);
+                        if let Some(peak) = peak {
+                            let peak = (f64::from(peak) * 32768.0)
+                                .round()
+                                .max(0.0)
+                                .min(f64::from(u16::MAX))
+                                as u16;
+                            channel.peak_bits = 16;
+                            channel.peak = peak.to_be_bytes().to_vec();
+                        }
+                        self.add_frame(Frame::with_content(
+                            "RVA2",
+                            Content::RelativeVolumeAdjustment(RelativeVolumeAdjustment {
+                                identification: identification.to_string(),
+                                channels: vec![channel],
+                            }),
+                        ));
+                    }
+                }
+                ReplayGainFormat::ITunesNormalization => {
+                    if let Some(gain) = replay_gain.track_gain {
+                        self.add_comment(Comment {
+                            lang: "eng".to_string(),
+                            description: ITUNES_NORMALIZATION.to_string(),
+                            text: format_itunes_normalization(gain, replay_gain.track_peak),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes the ReplayGain values in all formats, including the ID3v2.3 relative volume
+    /// adjustment (RVAD).
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_extended_text("REPLAYGAIN_TRACK_GAIN", "-6.50 dB");
+    /// tag.set_album_gain(-4.25);
+    /// tag.remove_replay_gain();
+    /// assert!(tag.replay_gain().is_none());
+    /// ```
+    pub fn remove_replay_gain(&mut self) {
+        self.frames.retain(|frame| match *frame.content() {
+            Content::ExtendedText(ref ext) => ![
+                REPLAYGAIN_TRACK_GAIN,
+                REPLAYGAIN_TRACK_PEAK,
+                REPLAYGAIN_ALBUM_GAIN,
+                REPLAYGAIN_ALBUM_PEAK,
+            ]
+            .iter()
+            .any(|description| ext.description.eq_ignore_ascii_case(description)),
+            Content::RelativeVolumeAdjustment(ref rva2) => {
+                !rva2.identification.eq_ignore_ascii_case("track")
+                    && !rva2.identification.eq_ignore_ascii_case("album")
+            }
+            Content::VolumeAdjustment(_) => false,
+            Content::Comment(ref comment) => comment.description != ITUNES_NORMALIZATION,
+            _ => true,
+        });
+    }
+
+    /// Returns the play counter (PCNT).
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// assert!(tag.play_count().is_none());
+    /// tag.set_play_count(12);
+    /// assert_eq!(tag.play_count(), Some(12));
+    /// ```
+    pub fn play_count(&self) -> Option<u64> {
+        self.get("PCNT")
+            .and_then(|frame| frame.content().play_counter())
+    }
+
+    /// Sets the play counter (PCNT).
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_play_count(12);
+    /// tag.set_play_count(13);
+    /// assert_eq!(tag.play_count(), Some(13));
+    /// ```
+    pub fn set_play_count(&mut self, count: u64) {
+        self.add_frame(Frame::with_content("PCNT", Content::PlayCounter(count)));
+    }
+
+    /// Removes the play counter (PCNT).
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_play_count(12);
+    /// tag.remove_play_count();
+    /// assert!(tag.play_count().is_none());
+    /// ```
+    pub fn remove_play_count(&mut self) {
+        self.remove("PCNT");
+    }
+
+    /// Returns the rating from the popularimeter (POPM) of the specified user.
+    ///
+    /// The rating ranges from 1 (worst) to 255 (best), 0 means that the file is unrated. Use
+    /// `RatingScale` to convert the rating to stars.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::RatingScale;
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_rating("alice@example.com", RatingScale::WindowsMediaPlayer.to_rating(4));
+    ///
+    /// let rating = tag.rating_for("alice@example.com").unwrap();
+    /// assert_eq!(RatingScale::WindowsMediaPlayer.to_stars(rating), 4);
+    /// assert!(tag.rating_for("bob@example.com").is_none());
+    /// ```
+    pub fn rating_for(&self, user: &str) -> Option<u8> {
+        self.popularimeters()
+            .find(|popm| popm.user == user)
+            .map(|popm| popm.rating)
+    }
+
+    /// Sets the rating in the popularimeter (POPM) of the specified user. The play counter of the
+    /// popularimeter is retained.
     ///
     /// # Example
     /// ```
     /// use id3::Tag;
+    /// use id3::frame::{Content, Frame, Popularimeter};
     ///
     /// let mut tag = Tag::new();
-    ///
-    /// tag.add_extended_text("key1", "value1");
-    /// tag.add_extended_text("key2", "value2");
-    ///
-    /// assert_eq!(tag.extended_texts().count(), 2);
-    /// assert!(tag.extended_texts().any(|t| t.description == "key1" && t.value == "value1"));
-    /// assert!(tag.extended_texts().any(|t| t.description == "key2" && t.value == "value2"));
-    /// ```
-    pub fn add_extended_text(&mut self, description: impl Into<String>, value: impl Into<String>) {
+    /// tag.add_frame(Frame::with_content("POPM", Content::Popularimeter(Popularimeter {
+    ///     user: "alice@example.com".to_string(),
+    ///     rating: 1,
+    ///     counter: 7,
+    /// })));
+    /// tag.set_rating("alice@example.com", 255);
+    ///
+    /// let popm = tag.popularimeters().next().unwrap();
+    /// assert_eq!(popm.rating, 255);
+    /// assert_eq!(popm.counter, 7);
+    /// ```
+    pub fn set_rating(&mut self, user: impl Into<String>, rating: u8) {
+        let user = user.into();
+        let counter = self
+            .popularimeters()
+            .find(|popm| popm.user == user)
+            .map(|popm| popm.counter)
+            .unwrap_or(0);
         let frame = Frame::with_content(
-            "TXXX",
-            Content::ExtendedText(ExtendedText {
-                description: description.into(),
-                value: value.into(),
+            "POPM",
+            Content::Popularimeter(Popularimeter {
+                user,
+                rating,
+                counter,
             }),
         );
         self.add_frame(frame);
     }
 
-    /// Removes the user defined text frame (TXXX) with the specified key and value.
-    ///
-    /// A key or value may be `None` to specify a wildcard value.
+    /// Removes the popularimeter (POPM) of the specified user.
     ///
     /// # Example
     /// ```
     /// use id3::Tag;
     ///
     /// let mut tag = Tag::new();
+    /// tag.set_rating("alice@example.com", 196);
+    /// tag.remove_rating("alice@example.com");
+    /// assert!(tag.rating_for("alice@example.com").is_none());
+    /// ```
+    pub fn remove_rating(&mut self, user: &str) {
+        self.frames.retain(|frame| match *frame.content() {
+            Content::Popularimeter(ref popm) => popm.user != user,
+            _ => true,
+        });
+    }
+
+    /// Adds terms of use (USER), replacing the terms of use in the same language.
     ///
-    /// tag.add_extended_text("key1", "value1");
-    /// tag.add_extended_text("key2", "value2");
-    /// tag.add_extended_text("key3", "value2");
-    /// tag.add_extended_text("key4", "value3");
-    /// tag.add_extended_text("key5", "value4");
-    /// assert_eq!(tag.extended_texts().count(), 5);
-    ///
-    /// tag.remove_extended_text(Some("key1"), None);
-    /// assert_eq!(tag.extended_texts().count(), 4);
-    ///
-    /// tag.remove_extended_text(None, Some("value2"));
-    /// assert_eq!(tag.extended_texts().count(), 2);
-    ///
-    /// tag.remove_extended_text(Some("key4"), Some("value3"));
-    /// assert_eq!(tag.extended_texts().count(), 1);
+    /// # Example
+    /// ```
+    /// use id3::Tag;
+    /// use id3::frame::TermsOfUse;
     ///
-    /// tag.remove_extended_text(None, None);
-    /// assert_eq!(tag.extended_texts().count(), 0);
+    /// let mut tag = Tag::new();
+    /// tag.add_terms_of_use(TermsOfUse {
+    ///     lang: "eng".to_string(),
+    ///     text: "All rights reserved".to_string(),
+    /// });
+    /// assert_eq!(tag.terms_of_use().nth(0).unwrap().text, "All rights reserved");
     /// ```
-    pub fn remove_extended_text(&mut self, description: Option<&str>, value: Option<&str>) {
-        self.frames.retain(|frame| {
-            if frame.id() == "TXXX" {
-                match *frame.content() {
-                    Content::ExtendedText(ref ext) => {
-                        let descr_match = description.map(|v| v == ext.description).unwrap_or(true);
-                        let value_match = value.map(|v| v == ext.value).unwrap_or(true);
-                        // True if we want to keep the frame.
-                        !(descr_match && value_match)
-                    }
-                    _ => {
-                        // A TXXX frame must always have content of the ExtendedText type. Remove
-                        // frames that do not fit this requirement.
-                        false
-                    }
-                }
-            } else {
-                true
-            }
-        });
+    pub fn add_terms_of_use(&mut self, terms_of_use: TermsOfUse) {
+        self.add_frame(Frame::with_content(
+            "USER",
+            Content::TermsOfUse(terms_of_use),
+        ));
     }
 
-    /// Adds a picture frame (APIC).
-    /// Any other pictures with the same type will be removed from the tag.
+    /// Removes all terms of use (USER) from the tag.
+    pub fn remove_all_terms_of_use(&mut self) {
+        self.remove("USER");
+    }
+
+    /// Returns the ownership (OWNE) of the file, if any.
     ///
     /// # Example
     /// ```
     /// use id3::Tag;
-    /// use id3::frame::{Picture, PictureType};
+    /// use id3::frame::Ownership;
     ///
     /// let mut tag = Tag::new();
-    /// tag.add_picture(Picture {
-    ///     mime_type: "image/jpeg".to_string(),
-    ///     picture_type: PictureType::Other,
-    ///     description: "some image".to_string(),
-    ///     data: vec![],
-    /// });
-    /// tag.add_picture(Picture {
-    ///     mime_type: "image/png".to_string(),
-    ///     picture_type: PictureType::Other,
-    ///     description: "some other image".to_string(),
-    ///     data: vec![],
+    /// assert!(tag.ownership().is_none());
+    /// tag.set_ownership(Ownership {
+    ///     price_paid: "USD0.99".to_string(),
+    ///     date_of_purchase: "20190103".to_string(),
+    ///     seller: "Example Store".to_string(),
     /// });
-    /// assert_eq!(tag.pictures().count(), 1);
-    /// assert_eq!(&tag.pictures().nth(0).unwrap().mime_type[..], "image/png");
+    /// assert_eq!(tag.ownership().unwrap().seller, "Example Store");
+    /// tag.remove_ownership();
+    /// assert!(tag.ownership().is_none());
     /// ```
-    pub fn add_picture(&mut self, picture: Picture) {
-        let frame = Frame::with_content("APIC", Content::Picture(picture));
-        self.add_frame(frame);
+    pub fn ownership(&self) -> Option<&Ownership> {
+        self.get("OWNE")
+            .and_then(|frame| frame.content().ownership())
     }
 
-    /// Removes all pictures of the specified type.
+    /// Sets the ownership (OWNE) of the file.
+    pub fn set_ownership(&mut self, ownership: Ownership) {
+        self.add_frame(Frame::with_content("OWNE", Content::Ownership(ownership)));
+    }
+
+    /// Removes the ownership (OWNE) of the file.
+    pub fn remove_ownership(&mut self) {
+        self.remove("OWNE");
+    }
+
+    /// Adds a commercial frame (COMR). Identical commercial frames are replaced.
     ///
     /// # Example
     /// ```
     /// use id3::Tag;
-    /// use id3::frame::{Picture, PictureType};
+    /// use id3::frame::{Commercial, ReceivedAs};
     ///
     /// let mut tag = Tag::new();
-    /// tag.add_picture(Picture {
-    ///     mime_type: "image/jpeg".to_string(),
-    ///     picture_type: PictureType::Other,
-    ///     description: "some image".to_string(),
-    ///     data: vec![],
-    /// });
-    /// tag.add_picture(Picture {
-    ///     mime_type: "image/png".to_string(),
-    ///     picture_type: PictureType::CoverFront,
-    ///     description: "some other image".to_string(),
-    ///     data: vec![],
+    /// tag.add_commercial(Commercial {
+    ///     price: "USD0.99".to_string(),
+    ///     valid_until: "20201231".to_string(),
+    ///     contact_url: "http://example.com".to_string(),
+    ///     received_as: ReceivedAs::FileOverInternet,
+    ///     seller: "Example Store".to_string(),
+    ///     description: "Single".to_string(),
+    ///     seller_logo: None,
     /// });
+    /// assert_eq!(tag.commercial_frames().count(), 1);
+    /// tag.remove_all_commercial();
+    /// assert_eq!(tag.commercial_frames().count(), 0);
+    /// ```
+    pub fn add_commercial(&mut self, commercial: Commercial) {
+        self.add_frame(Frame::with_content("COMR", Content::Commercial(commercial)));
+    }
+
+    /// Removes all commercial frames (COMR) from the tag.
+    pub fn remove_all_commercial(&mut self) {
+        self.remove("COMR");
+    }
+
+    /// Resolves the linked information frames (LINK) by adding the frames they refer to from
+    /// the tags of the linked files. Frames that are already present take precedence over the
+    /// linked frames. Returns the number of frames that were added.
     ///
-    /// assert_eq!(tag.pictures().count(), 2);
-    /// tag.remove_picture_by_type(PictureType::CoverFront);
-    /// assert_eq!(tag.pictures().count(), 1);
-    /// assert_eq!(tag.pictures().nth(0).unwrap().picture_type, PictureType::Other);
+    /// The `fetch` callback opens the file for a URL. Every file is read once. Note that writing
+    /// the resolved tag stores copies of the linked frames.
+    ///
+    /// # Example
     /// ```
-    pub fn remove_picture_by_type(&mut self, picture_type: PictureType) {
-        self.frames.retain(|frame| {
-            if frame.id() == "APIC" {
-                let pic = match *frame.content() {
-                    Content::Picture(ref picture) => picture,
-                    _ => return false,
-                };
-                return pic.picture_type != picture_type;
+    /// use id3::{Content, Frame, Tag, Version};
+    /// use id3::frame::LinkedInformation;
+    /// use std::io::Cursor;
+    ///
+    /// let mut album = Tag::new();
+    /// album.set_album("Album");
+    /// let mut album_data = Vec::new();
+    /// album.write_to(&mut album_data, Version::Id3v24).unwrap();
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.add_frame(Frame::with_content("LINK", Content::LinkedInformation(LinkedInformation {
+    ///     frame_identifier: "TALB".to_string(),
+    ///     url: "album.id3".to_string(),
+    ///     additional_data: Vec::new(),
+    /// })));
+    /// let added = tag
+    ///     .resolve_linked_information(|url| {
+    ///         assert_eq!(url, "album.id3");
+    ///         Ok(Cursor::new(album_data.clone()))
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(added, 1);
+    /// assert_eq!(tag.album(), Some("Album"));
+    /// ```
+    pub fn resolve_linked_information<F, R>(&mut self, mut fetch: F) -> crate::Result<usize>
+    where
+        F: FnMut(&str) -> io::Result<R>,
+        R: io::Read,
+    {
+        let links: Vec<LinkedInformation> = self.linked_information().cloned().collect();
+        let mut linked_tags: HashMap<String, Tag> = HashMap::new();
+        let mut added = 0;
+        for link in links {
+            if !linked_tags.contains_key(&link.url) {
+                let linked_tag = Tag::read_from(fetch(&link.url)?)?;
+                linked_tags.insert(link.url.clone(), linked_tag);
             }
-
-            true
-        });
+            let linked_frames = linked_tags[&link.url].frames().filter(|frame| {
+                frame.id() == link.frame_identifier
+                    && linked_data_matches(frame.content(), &link.additional_data)
+            });
+            for frame in linked_frames {
+                if !self.frames.contains(frame) {
+                    self.frames.push(frame.clone());
+                    added += 1;
+                }
+            }
+        }
+        Ok(added)
     }
 
     /// Adds a comment (COMM).
@@ -1176,6 +2363,48 @@ impl<'a> Tag {
         self.remove("SYLT");
     }
 
+    /// Returns the extended header of the tag, if any.
+    pub fn extended_header(&self) -> Option<&ExtendedHeader> {
+        self.extended_header.as_ref()
+    }
+
+    /// Sets the extended header of the tag.
+    ///
+    /// The extended header is written by `Tag::write_to` if it is set. Note that ID3v2.2 tags can
+    /// not contain an extended header.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::{ExtendedHeader, Tag};
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_extended_header(ExtendedHeader {
+    ///     is_update: true,
+    ///     ..ExtendedHeader::default()
+    /// });
+    /// assert!(tag.extended_header().unwrap().is_update);
+    /// ```
+    pub fn set_extended_header(&mut self, extended_header: ExtendedHeader) {
+        self.extended_header = Some(extended_header);
+    }
+
+    /// Removes the extended header of the tag.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::{ExtendedHeader, Tag};
+    ///
+    /// let mut tag = Tag::new();
+    /// tag.set_extended_header(ExtendedHeader::default());
+    /// assert!(tag.extended_header().is_some());
+    ///
+    /// tag.remove_extended_header();
+    /// assert!(tag.extended_header().is_none());
+    /// ```
+    pub fn remove_extended_header(&mut self) {
+        self.extended_header = None;
+    }
+
     /// Will return true if the reader is a candidate for an ID3 tag. The reader position will be
     /// reset back to the previous position before returning.
     pub fn is_candidate(mut reader: impl io::Read + io::Seek) -> crate::Result<bool> {
@@ -1196,20 +2425,200 @@ impl<'a> Tag {
     }
 
     /// Attempts to read an ID3 tag from the reader.
+    ///
+    /// Encrypted frames are kept as is, see `Tag::read_from_with_ciphers`.
     pub fn read_from(reader: impl io::Read) -> crate::Result<Tag> {
         stream::tag::decode(reader)
     }
 
+    /// Attempts to read an ID3 tag from the reader, decrypting encrypted frames with the
+    /// specified ciphers.
+    ///
+    /// Frames that are encrypted with a method for which no cipher is registered keep their
+    /// encrypted data as `Content::Unknown`.
+    pub fn read_from_with_ciphers(reader: impl io::Read, ciphers: &Ciphers) -> crate::Result<Tag> {
+        stream::tag::decode_with_ciphers(reader, ciphers)
+    }
+
+    /// Attempts to read an ID3v2.4 tag that has been appended to the end of the reader, before any
+    /// ID3v1 tag. Such tags are located by their footer.
+    pub fn read_from_appended(mut reader: impl io::Read + io::Seek) -> crate::Result<Tag> {
+        let location = storage::locate_id3v2_appended(&mut reader)?.ok_or_else(|| {
+            Error::new(
+                ErrorKind::NoTag,
+                "reader does not contain an appended id3 tag",
+            )
+        })?;
+        reader.seek(io::SeekFrom::Start(location.start))?;
+        stream::tag::decode(reader)
+    }
+
     /// Attempts to read an ID3 tag from the file at the indicated path.
+    ///
+    /// If the file does not start with a tag, an ID3v2.4 tag that is appended to the end of the
+    /// file is read instead.
     pub fn read_from_path(path: impl AsRef<Path>) -> crate::Result<Tag> {
+        let mut file = BufReader::new(File::open(path)?);
+        match Tag::read_from(&mut file) {
+            Err(Error {
+                kind: ErrorKind::NoTag,
+                ..
+            }) => {
+                file.seek(io::SeekFrom::Start(0))?;
+                Tag::read_from_appended(file)
+            }
+            rs => rs,
+        }
+    }
+
+    /// Attempts to read both the ID3v2 and ID3v1 tag from the reader and merges them into a single
+    /// tag according to the specified policy.
+    ///
+    /// The ID3v2 tag is read from the current position of the reader, the ID3v1 tag and its TAG+
+    /// extension from the end. The ID3v1 fields are mapped to the TIT2, TPE1, TALB, TYER, COMM,
+    /// TRCK and TCON frames. A TDRC frame in the ID3v2 tag is considered to be equivalent to
+    /// TYER.
+    ///
+    /// When the ID3v1 tag is preferred, its track number is combined with the total number of
+    /// tracks of the ID3v2 TRCK frame, if any.
+    ///
+    /// Along with the merged tag, each frame of the tag is returned with the kind of tag it
+    /// originates from, in the order of the frames of the tag.
+    ///
+    /// An error with the kind `ErrorKind::NoTag` is only returned if neither tag is present.
+    pub fn read_from_merged(
+        mut reader: impl io::Read + io::Seek,
+        policy: MergePolicy,
+    ) -> crate::Result<(Tag, Vec<(Frame, TagSource)>)> {
+        fn or_no_tag<T>(rs: crate::Result<T>) -> crate::Result<Option<T>> {
+            match rs {
+                Ok(tag) => Ok(Some(tag)),
+                Err(Error {
+                    kind: ErrorKind::NoTag,
+                    ..
+                }) => Ok(None),
+                Err(err) => Err(err),
+            }
+        }
+
+        let initial_position = reader.stream_position()?;
+        let tag_v2 = or_no_tag(stream::tag::decode(&mut reader))?;
+        reader.seek(io::SeekFrom::Start(initial_position))?;
+        let tag_v1 = or_no_tag(v1::Tag::read_from(&mut reader))?;
+        if tag_v2.is_none() && tag_v1.is_none() {
+            return Err(Error::new(
+                ErrorKind::NoTag,
+                "reader does not contain an id3 tag",
+            ));
+        }
+
+        let mut tag = tag_v2.unwrap_or_default();
+        let tag_v1 = match tag_v1 {
+            Some(tag_v1) => tag_v1,
+            None => {
+                let sources = tag
+                    .frames()
+                    .map(|frame| (frame.clone(), TagSource::Id3v2))
+                    .collect();
+                return Ok((tag, sources));
+            }
+        };
+        // The frames that were converted from the ID3v1 tag, the others originate from the ID3v2
+        // tag.
+        let mut v1_frames = HashMap::new();
+
+        let source_for = |extended: bool| {
+            if extended {
+                TagSource::Id3v1Extended
+            } else {
+                TagSource::Id3v1
+            }
+        };
+        let mut v1_sources = HashMap::new();
+        v1_sources.insert("TIT2", source_for(tag_v1.title.chars().count() > 30));
+        v1_sources.insert("TPE1", source_for(tag_v1.artist.chars().count() > 30));
+        v1_sources.insert("TALB", source_for(tag_v1.album.chars().count() > 30));
+        v1_sources.insert(
+            "TCON",
+            source_for(tag_v1.genre_str.iter().any(|g| !g.is_empty())),
+        );
+
+        for mut frame in Tag::from(tag_v1).frames {
+            let id = frame.id().to_string();
+            let present = match id.as_str() {
+                "TYER" => tag.get("TYER").is_some() || tag.get("TDRC").is_some(),
+                "COMM" => tag.comments().next().is_some(),
+                id => tag.get(id).is_some(),
+            };
+            if present && policy == MergePolicy::PreferId3v2 {
+                continue;
+            }
+            if id == "TYER" {
+                let year_v1 = frame.content().text().and_then(|t| t.parse().ok());
+                if tag.date_recorded().map(|ts| ts.year) == year_v1 {
+                    continue;
+                }
+                tag.remove("TDRC");
+            }
+            if id == "TRCK" {
+                if let (Some(track), Some(total_tracks)) = (
+                    frame.content().text().and_then(|t| t.parse::<u32>().ok()),
+                    tag.total_tracks(),
+                ) {
+                    frame = Frame::with_content(
+                        "TRCK",
+                        Content::Text(format!("{}/{}", track, total_tracks)),
+                    );
+                }
+            }
+            let source = v1_sources
+                .get(id.as_str())
+                .cloned()
+                .unwrap_or(TagSource::Id3v1);
+            tag.add_frame(frame.clone());
+            v1_frames.insert(frame, source);
+        }
+        let sources = tag
+            .frames()
+            .map(|frame| {
+                let source = v1_frames.get(frame).cloned().unwrap_or(TagSource::Id3v2);
+                (frame.clone(), source)
+            })
+            .collect();
+        Ok((tag, sources))
+    }
+
+    /// Attempts to read both the ID3v2 and ID3v1 tag from the file at the indicated path and
+    /// merges them into a single tag.
+    ///
+    /// See `read_from_merged` for details.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use id3::{MergePolicy, Tag, TagSource};
+    ///
+    /// let (tag, sources) = Tag::read_from_path_merged("music.mp3", MergePolicy::PreferId3v2).unwrap();
+    /// for (frame, source) in &sources {
+    ///     if *source == TagSource::Id3v1 {
+    ///         println!("{} from ID3v1: {}", frame.id(), frame);
+    ///     }
+    /// }
+    /// ```
+    pub fn read_from_path_merged(
+        path: impl AsRef<Path>,
+        policy: MergePolicy,
+    ) -> crate::Result<(Tag, Vec<(Frame, TagSource)>)> {
         let file = BufReader::new(File::open(path)?);
-        Tag::read_from(file)
+        Tag::read_from_merged(file, policy)
     }
 
     /// Attempts to write the ID3 tag to the writer using the specified version.
+    ///
+    /// The extended header is written as well if the tag has one.
     pub fn write_to(&self, writer: impl io::Write, version: Version) -> crate::Result<()> {
         stream::tag::EncoderBuilder::default()
             .version(version)
+            .extended_header(self.extended_header.is_some())
             .build()
             .unwrap()
             .encode(self, writer)
@@ -1217,9 +2626,13 @@ impl<'a> Tag {
 
     /// Attempts to write the ID3 tag from the file at the indicated path. If the specified path is
     /// the same path which the tag was read from, then the tag will be written to the padding if
-    /// possible.
+    /// possible. If the frames of a tag that a SEEK frame referred to were merged into this tag
+    /// when it was read, that tag is removed from the file as well.
     pub fn write_to_path(&self, path: impl AsRef<Path>, version: Version) -> crate::Result<()> {
         let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+        if self.seek_target_merged {
+            stream::tag::remove_seek_target(&mut file)?;
+        }
         let location = storage::locate_id3v2(&mut file)?.unwrap_or(0..0); // Create a new tag if none could be located.
 
         let mut storage = PlainStorage::new(file, location);
@@ -1244,6 +2657,16 @@ impl<'a> Tag {
         Ok(true)
     }
 
+    /// Marks the tag as containing the frames of the tag that its SEEK frame referred to.
+    pub(crate) fn set_seek_target_merged(&mut self) {
+        self.seek_target_merged = true;
+    }
+
+    /// Returns true if the tag contains the frames of the tag that its SEEK frame referred to.
+    pub(crate) fn seek_target_merged(&self) -> bool {
+        self.seek_target_merged
+    }
+
     /// Returns the `Content::Text` string for the frame with the specified identifier.
     /// Returns `None` if the frame with the specified ID can't be found or if the content is not
     /// `Content::Text`.
@@ -1318,6 +2741,29 @@ impl From<v1::Tag> for Tag {
     }
 }
 
+/// Returns whether a frame is identified by the additional data of a linked information frame.
+fn linked_data_matches(content: &Content, additional_data: &[String]) -> bool {
+    let data = match additional_data.first() {
+        Some(data) => data,
+        None => return true,
+    };
+    // Comments and lyrics are identified by the language followed by the description.
+    let (lang, description) = (data.get(..3), data.get(3..));
+    match *content {
+        Content::ExtendedText(ref text) => text.description == *data,
+        Content::Picture(ref picture) => picture.description == *data,
+        Content::EncapsulatedObject(ref object) => object.description == *data,
+        Content::Comment(ref comment) => {
+            lang == Some(&comment.lang) && description == Some(&comment.description)
+        }
+        Content::Lyrics(ref lyrics) => {
+            lang == Some(&lyrics.lang) && description == Some(&lyrics.description)
+        }
+        Content::SynchronisedLyrics(ref lyrics) => lang == Some(&lyrics.lang),
+        _ => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1343,4 +2789,261 @@ mod tests {
         tag_file.seek(io::SeekFrom::Start(0)).unwrap();
         assert!(!Tag::remove_from(&mut tag_file).unwrap());
     }
+
+    #[test]
+    fn replay_gain_itunes_normalization() {
+        let mut tag = Tag::new();
+        tag.add_comment(Comment {
+            lang: "eng".to_string(),
+            description: "iTunNORM".to_string(),
+            text: " 000003E8 000001F4 00000000 00000000 00000000 00000000 00004000 00002000 \
+                   00000000 00000000"
+                .to_string(),
+        });
+        let replay_gain = tag.replay_gain().unwrap();
+        assert_eq!(Some(0.0), replay_gain.track_gain);
+        assert_eq!(Some(0.5), replay_gain.track_peak);
+        assert_eq!(None, replay_gain.album_gain);
+
+        let replay_gain = ReplayGain {
+            track_gain: Some(-6.0),
+            track_peak: Some(0.25),
+            album_gain: Some(-4.0),
+            album_peak: None,
+        };
+        tag.set_replay_gain(replay_gain, &[ReplayGainFormat::ITunesNormalization]);
+        assert_eq!(1, tag.comments().count());
+        let read = tag.replay_gain().unwrap();
+        assert!((read.track_gain.unwrap() + 6.0).abs() < 0.01);
+        assert_eq!(Some(0.25), read.track_peak);
+        assert_eq!(None, read.album_gain);
+
+        // Values from extended texts take precedence.
+        tag.add_extended_text("REPLAYGAIN_TRACK_GAIN", "+1.00 dB");
+        assert_eq!(Some(1.0), tag.replay_gain().unwrap().track_gain);
+        assert_eq!(Some(0.25), tag.replay_gain().unwrap().track_peak);
+    }
+
+    #[test]
+    fn replay_gain_volume_adjustment() {
+        use crate::frame::{VolumeAdjustment, VolumeChange};
+
+        let mut tag = Tag::new();
+        let rvad = VolumeAdjustment {
+            bits: 16,
+            right: VolumeChange::with_gain(-6.0),
+            left: VolumeChange::with_gain(-6.0),
+            back_right: None,
+            back_left: None,
+            centre: None,
+            bass: None,
+        };
+        tag.add_frame(Frame::with_content(
+            "RVAD",
+            Content::VolumeAdjustment(rvad.clone()),
+        ));
+        let replay_gain = tag.replay_gain().unwrap();
+        assert!((replay_gain.track_gain.unwrap() + 6.0).abs() < 0.02);
+        assert_eq!(None, replay_gain.track_peak);
+
+        // The RVAD frame is replaced along with the other ReplayGain values.
+        let replay_gain = ReplayGain {
+            track_gain: Some(-3.0),
+            ..ReplayGain::default()
+        };
+        tag.set_replay_gain(replay_gain, &[ReplayGainFormat::ExtendedText]);
+        assert!(tag.get("RVAD").is_none());
+        assert_eq!(Some(replay_gain), tag.replay_gain());
+
+        tag.add_frame(Frame::with_content("RVAD", Content::VolumeAdjustment(rvad)));
+        tag.remove_replay_gain();
+        assert!(tag.get("RVAD").is_none());
+        assert!(tag.replay_gain().is_none());
+    }
+
+    #[test]
+    fn attach_and_extract_file() {
+        let tmp = tempdir::TempDir::new("id3_geob").unwrap();
+        let tmp_name = tmp.path().join("image.jpg");
+        let mut tag = Tag::new();
+        tag.attach_file("testdata/image.jpg", "image/jpeg", "artwork source")
+            .unwrap();
+        assert!(tag
+            .extract_encapsulated_object("missing", &tmp_name)
+            .is_err());
+        tag.extract_encapsulated_object("artwork source", &tmp_name)
+            .unwrap();
+        assert_eq!(
+            fs::read("testdata/image.jpg").unwrap(),
+            fs::read(&tmp_name).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_linked_information() {
+        let mut album = Tag::new();
+        album.add_picture(Picture {
+            mime_type: "image/jpeg".to_string(),
+            picture_type: PictureType::CoverFront,
+            description: "booklet".to_string(),
+            data: fs::read("testdata/image.jpg").unwrap(),
+        });
+        album.add_comment(Comment {
+            lang: "eng".to_string(),
+            description: "note".to_string(),
+            text: "Shared".to_string(),
+        });
+        album.add_comment(Comment {
+            lang: "eng".to_string(),
+            description: "other".to_string(),
+            text: "Not linked".to_string(),
+        });
+        let mut album_data = Vec::new();
+        album.write_to(&mut album_data, Version::Id3v24).unwrap();
+
+        let link = |id: &str, data: &str| {
+            Frame::with_content(
+                "LINK",
+                Content::LinkedInformation(LinkedInformation {
+                    frame_identifier: id.to_string(),
+                    url: "album.id3".to_string(),
+                    additional_data: vec![data.to_string()],
+                }),
+            )
+        };
+        let mut tag = Tag::new();
+        tag.add_frame(link("APIC", "booklet"));
+        tag.add_frame(link("COMM", "engnote"));
+        let mut fetched = 0;
+        let added = tag
+            .resolve_linked_information(|url| {
+                assert_eq!("album.id3", url);
+                fetched += 1;
+                Ok(io::Cursor::new(album_data.clone()))
+            })
+            .unwrap();
+        assert_eq!(1, fetched);
+        assert_eq!(2, added);
+        assert_eq!("booklet", tag.pictures().next().unwrap().description);
+        assert_eq!(
+            vec!["Shared"],
+            tag.comments().map(|c| &c.text[..]).collect::<Vec<_>>()
+        );
+
+        // Frames that are present already are not replaced.
+        let added = tag
+            .resolve_linked_information(|_| Ok(io::Cursor::new(album_data.clone())))
+            .unwrap();
+        assert_eq!(0, added);
+        assert!(tag
+            .resolve_linked_information(|_| Err::<io::Cursor<Vec<u8>>, _>(io::Error::new(
+                io::ErrorKind::NotFound,
+                "missing"
+            )))
+            .is_err());
+    }
+
+    fn write_merge_file(path: &Path) {
+        let mut tag = Tag::new();
+        tag.set_title("Title v2");
+        tag.set_text("TRCK", "5/12");
+        tag.set_date_recorded(Timestamp {
+            year: 2017,
+            month: Some(1),
+            day: None,
+            hour: None,
+            minute: None,
+            second: None,
+        });
+        let mut data = Vec::new();
+        tag.write_to(&mut data, Version::Id3v24).unwrap();
+        data.extend(vec![0xff; 1024]);
+        fs::write(path, data).unwrap();
+
+        let tag_v1 = v1::Tag {
+            title: "Title v1".to_string(),
+            artist: "An artist that is a lot longer than thirty characters".to_string(),
+            album: "Album".to_string(),
+            year: "2017".to_string(),
+            comment: "Comment".to_string(),
+            track: Some(3),
+            genre_id: 31,
+            ..v1::Tag::default()
+        };
+        tag_v1.write_to_path(path).unwrap();
+    }
+
+    fn source_of(sources: &[(Frame, TagSource)], id: &str) -> Option<TagSource> {
+        sources
+            .iter()
+            .find(|(frame, _)| frame.id() == id)
+            .map(|(_, source)| *source)
+    }
+
+    #[test]
+    fn read_merged_prefer_id3v2() {
+        let tmp = tempdir::TempDir::new("id3_merge").unwrap();
+        let tmp_name = tmp.path().join("read_merged_prefer_id3v2");
+        write_merge_file(&tmp_name);
+
+        let (tag, sources) =
+            Tag::read_from_path_merged(&tmp_name, MergePolicy::PreferId3v2).unwrap();
+        assert_eq!("Title v2", tag.title().unwrap());
+        assert_eq!(
+            "An artist that is a lot longer than thirty characters",
+            tag.artist().unwrap()
+        );
+        assert_eq!("Album", tag.album().unwrap());
+        assert_eq!("Trance", tag.genre().unwrap());
+        assert_eq!(5, tag.track().unwrap());
+        assert_eq!(12, tag.total_tracks().unwrap());
+        assert_eq!("Comment", tag.comments().next().unwrap().text);
+        assert!(tag.get("TYER").is_none());
+        assert_eq!(2017, tag.date_recorded().unwrap().year);
+
+        assert_eq!(Some(TagSource::Id3v2), source_of(&sources, "TIT2"));
+        assert_eq!(Some(TagSource::Id3v2), source_of(&sources, "TDRC"));
+        assert_eq!(Some(TagSource::Id3v1Extended), source_of(&sources, "TPE1"));
+        assert_eq!(Some(TagSource::Id3v1), source_of(&sources, "TALB"));
+        assert_eq!(Some(TagSource::Id3v1), source_of(&sources, "TCON"));
+        assert_eq!(Some(TagSource::Id3v2), source_of(&sources, "TRCK"));
+        assert_eq!(Some(TagSource::Id3v1), source_of(&sources, "COMM"));
+        assert_eq!(tag.frames().count(), sources.len());
+    }
+
+    #[test]
+    fn read_merged_prefer_id3v1() {
+        let tmp = tempdir::TempDir::new("id3_merge").unwrap();
+        let tmp_name = tmp.path().join("read_merged_prefer_id3v1");
+        write_merge_file(&tmp_name);
+
+        let (tag, sources) =
+            Tag::read_from_path_merged(&tmp_name, MergePolicy::PreferId3v1).unwrap();
+        assert_eq!("Title v1", tag.title().unwrap());
+        assert_eq!(Some(TagSource::Id3v1), source_of(&sources, "TIT2"));
+        assert_eq!(3, tag.track().unwrap());
+        assert_eq!(12, tag.total_tracks().unwrap());
+        assert_eq!(Some(TagSource::Id3v1), source_of(&sources, "TRCK"));
+        assert_eq!(tag.frames().count(), sources.len());
+        // The years are equal, so the more precise TDRC frame is retained.
+        assert_eq!(Some(1), tag.date_recorded().unwrap().month);
+        assert!(tag.get("TYER").is_none());
+    }
+
+    #[test]
+    fn read_merged_no_tag() {
+        let rs = Tag::read_from_merged(io::Cursor::new(vec![0xff; 512]), MergePolicy::PreferId3v2);
+        match rs {
+            Err(Error {
+                kind: ErrorKind::NoTag,
+                ..
+            }) => (),
+            _ => panic!("expected NoTag"),
+        }
+
+        let (tag, sources) =
+            Tag::read_from_path_merged("testdata/id3v1.id3", MergePolicy::PreferId3v2).unwrap();
+        assert_eq!("Title", tag.title().unwrap());
+        assert!(sources.iter().all(|(_, s)| *s == TagSource::Id3v1));
+    }
 }
@@ -1,8 +1,9 @@
 use crate::{Error, ErrorKind};
 use std::cmp;
 use std::fs;
-use std::io::{self, Read, Seek};
+use std::io::{self, Read, Seek, Write};
 use std::ops;
+use std::path::Path;
 
 /// Location of the ID3v1 tag chunk relative to the end of the file.
 static TAG_CHUNK: ops::Range<i64> = -128..0;
@@ -176,6 +177,9 @@ pub struct Tag {
     /// A free-form comment.
     pub comment: String,
     /// Number of the track. ID3v1.1 data.
+    ///
+    /// A track number of 0 denotes the absence of a track number, so `Some(0)` is written as
+    /// `None`.
     pub track: Option<u8>,
     /// The genre mapping is standardized up to 79, altough this implementation uses the Winamp
     /// extended genre list:
@@ -192,12 +196,127 @@ pub struct Tag {
     pub end_time: Option<String>,
 }
 
+/// Identifies a field of an ID3v1 tag.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Field {
+    Title,
+    Artist,
+    Album,
+    Year,
+    Comment,
+    Track,
+    Genre,
+}
+
 impl Tag {
     /// Creates a new empty ID3v1 tag.
     pub fn new() -> Tag {
         Tag::default()
     }
 
+    /// Creates an ID3v1 tag from the contents of an ID3v2 tag.
+    ///
+    /// The following frames are mapped onto the ID3v1 fields:
+    ///
+    /// * TIT2, TPE1 and TALB to the title, artist and album. Data that does not fit in the ID3v1
+    ///   fields is stored in the TAG+ extension.
+    /// * TYER, falling back to the year of TDRC, to the year.
+    /// * The first COMM frame without a description, falling back to the first COMM frame, to the
+    ///   comment.
+    /// * TRCK to the ID3v1.1 track number.
+    /// * TCON to the genre ID if the genre is present in the genre list, otherwise the genre is
+    ///   stored in the TAG+ extension.
+    ///
+    /// Because the ID3v1 format is a lot more limited than ID3v2, the conversion may be lossy.
+    /// Along with the new tag, a list of the fields that could not hold all data of their
+    /// respective frames is returned. A field is also considered lossy if it contains characters
+    /// that can not be represented in ISO-8859-1.
+    ///
+    /// # Example
+    /// ```
+    /// use id3::v1;
+    ///
+    /// let mut tag = id3::Tag::new();
+    /// tag.set_title("Title");
+    /// tag.set_genre("Trance");
+    /// tag.set_text("TRCK", "1/12");
+    ///
+    /// let (tag_v1, lossy) = v1::Tag::from_v2(&tag);
+    /// assert_eq!(tag_v1.title, "Title");
+    /// assert_eq!(tag_v1.genre_id, 31);
+    /// assert_eq!(tag_v1.track, Some(1));
+    /// assert_eq!(lossy, vec![v1::Field::Track]);
+    /// ```
+    pub fn from_v2(tag: &crate::Tag) -> (Tag, Vec<Field>) {
+        let mut lossy = Vec::new();
+        let mut tag_v1 = Tag::new();
+        let mut convert = |field: Field, value: Option<&str>, max_len: usize| -> String {
+            let (s, lost) = fit_str(value.unwrap_or(""), max_len);
+            if lost {
+                lossy.push(field);
+            }
+            s
+        };
+
+        tag_v1.title = convert(Field::Title, tag.title(), 90);
+        tag_v1.artist = convert(Field::Artist, tag.artist(), 90);
+        tag_v1.album = convert(Field::Album, tag.album(), 90);
+
+        let year = tag.year().or_else(|| tag.date_recorded().map(|ts| ts.year));
+        tag_v1.year = match year {
+            Some(year) if (0..=9999).contains(&year) => format!("{:04}", year),
+            Some(_) => {
+                lossy.push(Field::Year);
+                String::new()
+            }
+            None => String::new(),
+        };
+
+        tag_v1.track = match tag.track() {
+            Some(track) if (1..=0xff).contains(&track) => {
+                if tag.total_tracks().is_some() {
+                    lossy.push(Field::Track);
+                }
+                Some(track as u8)
+            }
+            Some(_) => {
+                lossy.push(Field::Track);
+                None
+            }
+            None => None,
+        };
+
+        let comment = tag
+            .comments()
+            .find(|c| c.description.is_empty())
+            .or_else(|| tag.comments().next());
+        let (comment, lost) = fit_str(
+            comment.map(|c| c.text.as_str()).unwrap_or(""),
+            if tag_v1.track.is_some() { 28 } else { 30 },
+        );
+        tag_v1.comment = comment;
+        if lost {
+            lossy.push(Field::Comment);
+        }
+
+        tag_v1.genre_id = 0xff;
+        if let Some(genre) = tag.genre() {
+            match genre_id_for_name(genre) {
+                Some(id) => tag_v1.genre_id = id,
+                None => {
+                    let (genre_str, lost) = fit_str(genre, 30);
+                    tag_v1.genre_str = Some(genre_str);
+                    if lost {
+                        lossy.push(Field::Genre);
+                    }
+                }
+            }
+        }
+
+        (tag_v1, lossy)
+    }
+
     /// Checks whether the reader contains an ID3v1 tag.
     ///
     /// The reader position will be reset back to the previous position before returning.
@@ -272,8 +391,8 @@ impl Tag {
         let (speed, genre_str, start_time, end_time) = if let Some(xt) = xtag {
             let speed = if xt[184] == 0 { None } else { Some(xt[184]) };
             let genre_str = decode_str(&xt[185..215], None);
-            let start_time = decode_str(&xt[185..215], None);
-            let end_time = decode_str(&xt[185..215], None);
+            let start_time = decode_str(&xt[215..221], None);
+            let end_time = decode_str(&xt[221..227], None);
             (speed, Some(genre_str), Some(start_time), Some(end_time))
         } else {
             (None, None, None, None)
@@ -336,6 +455,34 @@ impl Tag {
         Ok(truncate_to.is_some())
     }
 
+    /// Writes the tag to the end of the file, replacing any ID3v1 tag that is already present.
+    ///
+    /// The extended TAG+ block is only written if the tag contains data that does not fit in a
+    /// plain ID3v1.1 tag. That is the case when the title, artist or album is longer than 30
+    /// bytes, or when one of the `speed`, `genre_str`, `start_time` or `end_time` fields is set.
+    ///
+    /// Characters that can not be represented in ISO-8859-1 are replaced with `?`. Data that
+    /// exceeds the size of a field is truncated.
+    pub fn write_to(&self, file: &mut fs::File) -> crate::Result<()> {
+        Tag::remove(file)?;
+        file.seek(io::SeekFrom::End(0))?;
+        if self.has_extended_data() {
+            file.write_all(&self.encode_extended())?;
+        }
+        file.write_all(&self.encode())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Writes the tag to the file at the indicated path, replacing any ID3v1 tag that is already
+    /// present.
+    ///
+    /// See `write_to` for details.
+    pub fn write_to_path(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+        self.write_to(&mut file)
+    }
+
     /// Returns `genre_str`, falling back to translating `genre_id` to a string.
     pub fn genre(&self) -> Option<&str> {
         if let Some(ref g) = self.genre_str {
@@ -345,28 +492,114 @@ impl Tag {
         }
         GENRE_LIST.get(self.genre_id as usize).cloned()
     }
-}
 
-#[cfg(all(test, feature = "unstable"))]
-mod benchmarks {
-    extern crate test;
-    use super::*;
-    use std::fs;
-    use std::io::Read;
+    /// Returns true if the tag can not be stored without the TAG+ extension.
+    fn has_extended_data(&self) -> bool {
+        encode_str(&self.title).len() > 30
+            || encode_str(&self.artist).len() > 30
+            || encode_str(&self.album).len() > 30
+            || self.speed.is_some()
+            || self.genre_str.is_some()
+            || self.start_time.is_some()
+            || self.end_time.is_some()
+    }
 
-    #[bench]
-    fn read_id3v1(b: &mut test::Bencher) {
-        let mut buf = Vec::new();
-        fs::File::open("testdata/id3v1.id3")
-            .unwrap()
-            .read_to_end(&mut buf)
-            .unwrap();
-        b.iter(|| {
-            Tag::read_from(io::Cursor::new(buf.as_slice())).unwrap();
-        });
+    /// Encodes the 128 byte ID3v1 tag chunk.
+    fn encode(&self) -> [u8; 128] {
+        let mut buf = [0; 128];
+        buf[0..3].copy_from_slice(b"TAG");
+        copy_str(&mut buf[3..33], &encode_str(&self.title));
+        copy_str(&mut buf[33..63], &encode_str(&self.artist));
+        copy_str(&mut buf[63..93], &encode_str(&self.album));
+        copy_str(&mut buf[93..97], &encode_str(&self.year));
+        match self.track {
+            Some(track) if track != 0 => {
+                copy_str(&mut buf[97..125], &encode_str(&self.comment));
+                buf[125] = 0;
+                buf[126] = track;
+            }
+            _ => copy_str(&mut buf[97..127], &encode_str(&self.comment)),
+        }
+        buf[127] = self.genre_id;
+        buf
+    }
+
+    /// Encodes the 227 byte TAG+ extended tag chunk. The title, artist and album fields contain
+    /// the parts that did not fit in the ID3v1 tag chunk.
+    fn encode_extended(&self) -> [u8; 227] {
+        fn overflow(bytes: &[u8]) -> &[u8] {
+            bytes.get(30..).unwrap_or(&[])
+        }
+        let mut buf = [0; 227];
+        buf[0..4].copy_from_slice(b"TAG+");
+        copy_str(&mut buf[4..64], overflow(&encode_str(&self.title)));
+        copy_str(&mut buf[64..124], overflow(&encode_str(&self.artist)));
+        copy_str(&mut buf[124..184], overflow(&encode_str(&self.album)));
+        buf[184] = self.speed.unwrap_or(0);
+        if let Some(ref genre_str) = self.genre_str {
+            copy_str(&mut buf[185..215], &encode_str(genre_str));
+        }
+        if let Some(ref start_time) = self.start_time {
+            copy_str(&mut buf[215..221], &encode_str(start_time));
+        }
+        if let Some(ref end_time) = self.end_time {
+            copy_str(&mut buf[221..227], &encode_str(end_time));
+        }
+        buf
     }
 }
 
+/// Looks up the ID of a genre in the genre list.
+///
+/// Besides genre names, the ID3v2.3 style "(31)" references and plain numbers are accepted.
+fn genre_id_for_name(genre: &str) -> Option<u8> {
+    let genre = genre.trim();
+    let reference = genre
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .parse::<u8>()
+        .ok()
+        .filter(|id| (*id as usize) < GENRE_LIST.len());
+    reference.or_else(|| {
+        GENRE_LIST
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(genre))
+            .map(|id| id as u8)
+    })
+}
+
+/// Truncates a string so it fits in `max_len` bytes of ISO-8859-1 and replaces any characters
+/// that can not be represented. Returns true as second value if data was lost in the process.
+fn fit_str(s: &str, max_len: usize) -> (String, bool) {
+    let mut lost = false;
+    let fitted = s
+        .chars()
+        .map(|c| {
+            if (c as u32) < 0x100 {
+                c
+            } else {
+                lost = true;
+                '?'
+            }
+        })
+        .take(max_len)
+        .collect();
+    (fitted, lost || s.chars().count() > max_len)
+}
+
+/// Encodes a string as ISO-8859-1, replacing characters which can not be represented.
+fn encode_str(s: &str) -> Vec<u8> {
+    s.chars()
+        .map(|c| if (c as u32) < 0x100 { c as u8 } else { b'?' })
+        .collect()
+}
+
+/// Copies as much of `src` as fits into the null-padded field `dst`.
+fn copy_str(dst: &mut [u8], src: &[u8]) {
+    let len = cmp::min(dst.len(), src.len());
+    dst[..len].copy_from_slice(&src[..len]);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,4 +642,163 @@ mod tests {
         tag_file.seek(io::SeekFrom::Start(0)).unwrap();
         assert!(!Tag::remove(&mut tag_file).unwrap());
     }
+
+    #[test]
+    fn write_id3v1() {
+        let tmp = tempdir::TempDir::new("id3_v1").unwrap();
+        let tmp_name = tmp.path().join("write_id3v1_tag");
+        {
+            let mut tag_file = fs::File::create(&tmp_name).unwrap();
+            let mut original = fs::File::open("testdata/id3v1.id3").unwrap();
+            io::copy(&mut original, &mut tag_file).unwrap();
+        }
+        let original_len = fs::metadata(&tmp_name).unwrap().len();
+
+        let mut tag = Tag::read_from(fs::File::open(&tmp_name).unwrap()).unwrap();
+        tag.title = "New Title".to_string();
+        tag.track = Some(7);
+        tag.write_to_path(&tmp_name).unwrap();
+
+        assert_eq!(original_len, fs::metadata(&tmp_name).unwrap().len());
+        let tag_read = Tag::read_from(fs::File::open(&tmp_name).unwrap()).unwrap();
+        assert_eq!(tag, tag_read);
+    }
+
+    #[test]
+    fn write_id3v1_track_zero() {
+        let tmp = tempdir::TempDir::new("id3_v1").unwrap();
+        let tmp_name = tmp.path().join("write_id3v1_track_zero");
+        fs::write(&tmp_name, vec![0xff; 512]).unwrap();
+
+        let tag = Tag {
+            title: "Title".to_string(),
+            comment: "A comment of exactly thirty ch".to_string(),
+            track: Some(0),
+            ..Tag::default()
+        };
+        tag.write_to_path(&tmp_name).unwrap();
+
+        let tag_read = Tag::read_from(fs::File::open(&tmp_name).unwrap()).unwrap();
+        assert_eq!(None, tag_read.track);
+        assert_eq!(tag.comment, tag_read.comment);
+    }
+
+    #[test]
+    fn write_id3v1_extended() {
+        let tmp = tempdir::TempDir::new("id3_v1").unwrap();
+        let tmp_name = tmp.path().join("write_id3v1_extended_tag");
+        fs::write(&tmp_name, vec![0xff; 512]).unwrap();
+
+        let tag = Tag {
+            title: "A title that is a lot longer than thirty characters".to_string(),
+            artist: "Artist".to_string(),
+            album: "Album".to_string(),
+            year: "2019".to_string(),
+            comment: "Comment".to_string(),
+            track: None,
+            genre_id: 255,
+            speed: Some(2),
+            genre_str: Some("Math Rock".to_string()),
+            start_time: Some("000:05".to_string()),
+            end_time: Some("004:20".to_string()),
+        };
+        tag.write_to_path(&tmp_name).unwrap();
+        tag.write_to_path(&tmp_name).unwrap();
+
+        assert_eq!(512 + 355, fs::metadata(&tmp_name).unwrap().len());
+        let tag_read = Tag::read_from(fs::File::open(&tmp_name).unwrap()).unwrap();
+        assert_eq!(tag, tag_read);
+        assert_eq!("Math Rock", tag_read.genre().unwrap());
+    }
+
+    #[test]
+    fn from_v2() {
+        let mut tag = crate::Tag::new();
+        tag.set_title("A title that is a lot longer than thirty characters");
+        tag.set_artist("Ärtist");
+        tag.set_album("Albüm ✓");
+        tag.set_year(2019);
+        tag.set_track(300);
+        tag.add_comment(crate::frame::Comment {
+            lang: "eng".to_string(),
+            description: "description".to_string(),
+            text: "Other comment".to_string(),
+        });
+        tag.add_comment(crate::frame::Comment {
+            lang: "eng".to_string(),
+            description: "".to_string(),
+            text: "Comment".to_string(),
+        });
+        tag.set_genre("(17)");
+
+        let (tag_v1, lossy) = Tag::from_v2(&tag);
+        assert_eq!(
+            "A title that is a lot longer than thirty characters",
+            tag_v1.title
+        );
+        assert_eq!("Ärtist", tag_v1.artist);
+        assert_eq!("Albüm ?", tag_v1.album);
+        assert_eq!("2019", tag_v1.year);
+        assert_eq!("Comment", tag_v1.comment);
+        assert_eq!(None, tag_v1.track);
+        assert_eq!(17, tag_v1.genre_id);
+        assert_eq!(None, tag_v1.genre_str);
+        assert_eq!(vec![Field::Album, Field::Track], lossy);
+    }
+
+    #[test]
+    fn from_v2_genre_str() {
+        let mut tag = crate::Tag::new();
+        tag.set_genre("A genre that is not in the list at all");
+        tag.add_comment(crate::frame::Comment {
+            lang: "eng".to_string(),
+            description: "".to_string(),
+            text: "A comment that is a lot longer than thirty characters".to_string(),
+        });
+
+        let (tag_v1, lossy) = Tag::from_v2(&tag);
+        assert_eq!(255, tag_v1.genre_id);
+        assert_eq!(
+            "A genre that is not in the lis",
+            tag_v1.genre_str.as_ref().unwrap()
+        );
+        assert_eq!("A comment that is a lot longer", tag_v1.comment);
+        assert_eq!(vec![Field::Comment, Field::Genre], lossy);
+
+        let mut tag = crate::Tag::new();
+        tag.set_genre("trance");
+        let (tag_v1, lossy) = Tag::from_v2(&tag);
+        assert_eq!(31, tag_v1.genre_id);
+        assert!(lossy.is_empty());
+    }
+
+    #[test]
+    fn genre_id_for_name_references() {
+        assert_eq!(Some(31), genre_id_for_name("Trance"));
+        assert_eq!(Some(31), genre_id_for_name(" trance "));
+        assert_eq!(Some(17), genre_id_for_name("(17)"));
+        assert_eq!(Some(17), genre_id_for_name("17"));
+        assert_eq!(None, genre_id_for_name("(200)"));
+        assert_eq!(None, genre_id_for_name("Not a genre"));
+    }
+}
+
+#[cfg(all(test, feature = "unstable"))]
+mod benchmarks {
+    extern crate test;
+    use super::*;
+    use std::fs;
+    use std::io::Read;
+
+    #[bench]
+    fn read_id3v1(b: &mut test::Bencher) {
+        let mut buf = Vec::new();
+        fs::File::open("testdata/id3v1.id3")
+            .unwrap()
+            .read_to_end(&mut buf)
+            .unwrap();
+        b.iter(|| {
+            Tag::read_from(io::Cursor::new(buf.as_slice())).unwrap();
+        });
+    }
 }